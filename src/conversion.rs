@@ -0,0 +1,132 @@
+/**
+ * How a column type change is applied by PostgreSQL.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Conversion {
+    /** Same storage, only the catalog is updated. */
+    BinaryCoercible,
+    /** Lossless implicit cast, the table is rewritten. */
+    Widening,
+    /** Explicit cast with a `USING` expression, the table is rewritten. */
+    Cast,
+}
+
+/**
+ * Classifies the change of a column from the `old` type to the `new` one, as
+ * returned by `Column::ty`.
+ */
+pub fn classify(old: &str, new: &str) -> Conversion {
+    let old = Type::parse(old);
+    let new = Type::parse(new);
+
+    if old.array != new.array {
+        return Conversion::Cast;
+    }
+
+    match (old.name, new.name) {
+        ("varchar" | "varbit", _) if old.name == new.name && !new.is_shorter(&old) => {
+            Conversion::BinaryCoercible
+        }
+        // A length limit adds a coercion, `text` to `varchar(n)` rewrites the
+        // table like any other function call
+        ("varchar", "text") | ("text", "varchar") if new.len.is_none() => {
+            Conversion::BinaryCoercible
+        }
+        ("numeric", "numeric")
+            if new.len.is_none() || (new.scale == old.scale && !new.is_shorter(&old)) =>
+        {
+            Conversion::BinaryCoercible
+        }
+        ("cidr", "inet") | ("xml", "text") => Conversion::BinaryCoercible,
+        ("int2", "int4" | "int8" | "numeric")
+        | ("int4", "int8" | "numeric")
+        | ("int8", "numeric")
+        | ("float4", "float8")
+        | ("date", "timestamp")
+            if new.len.is_none() =>
+        {
+            Conversion::Widening
+        }
+        _ => Conversion::Cast,
+    }
+}
+
+/**
+ * Returns `true` if some values of the `old` type can’t be stored in the
+ * `new` one without being truncated, rounded or rejected.
+ */
+pub fn is_lossy(old: &str, new: &str) -> bool {
+    let old = Type::parse(old);
+    let new = Type::parse(new);
+
+    match (old.name, new.name) {
+        ("varchar" | "bpchar" | "varbit" | "bit", _) if old.name == new.name => {
+            new.is_shorter(&old)
+        }
+        ("text" | "varchar" | "bpchar", "varchar" | "bpchar") => new.is_shorter(&old),
+        // Fewer digits before or after the decimal point
+        ("numeric", "numeric") => match (old.len, new.len) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(len), Some(new_len)) => {
+                let (scale, new_scale) = (old.scale.unwrap_or(0), new.scale.unwrap_or(0));
+
+                new_scale < scale || new_len - new_scale < len - scale
+            }
+        },
+        ("int8", "int4" | "int2") | ("int4", "int2") | ("float8", "float4") => true,
+        ("numeric" | "float4" | "float8", "int2" | "int4" | "int8") => true,
+        ("timestamptz", "timestamp" | "date" | "time") | ("timestamp", "date" | "time") => true,
+        _ => false,
+    }
+}
+
+struct Type<'a> {
+    name: &'a str,
+    /**
+     * Length, or precision of a `numeric`.
+     */
+    len: Option<i32>,
+    scale: Option<i32>,
+    array: bool,
+}
+
+impl<'a> Type<'a> {
+    fn parse(ty: &'a str) -> Self {
+        let (ty, array) = match ty.strip_suffix("[]") {
+            Some(ty) => (ty, true),
+            None => (ty, false),
+        };
+
+        let (name, modifiers) = match ty.split_once('(') {
+            Some((name, modifiers)) => (name, Some(modifiers.trim_end_matches(')'))),
+            None => (ty, None),
+        };
+
+        let (len, scale) = match modifiers {
+            Some(modifiers) => match modifiers.split_once(',') {
+                Some((len, scale)) => (len.trim().parse().ok(), scale.trim().parse().ok()),
+                None => (modifiers.parse().ok(), None),
+            },
+            None => (None, None),
+        };
+
+        Self {
+            name,
+            len,
+            scale,
+            array,
+        }
+    }
+
+    /**
+     * Unbounded types are the longest.
+     */
+    fn is_shorter(&self, other: &Self) -> bool {
+        match (self.len, other.len) {
+            (Some(len), Some(other)) => len < other,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
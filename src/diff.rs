@@ -3,19 +3,21 @@ use std::collections::BTreeMap;
 trait Comparable: std::fmt::Debug + Eq {}
 
 trait Stack<C: Comparable, CH>: Default {
+    fn new(options: &crate::Options) -> Self;
     fn add(&mut self, new: &C);
     fn remove(&mut self, old: &C);
     fn update(&mut self, old: &C, new: &C);
     fn add_child(&mut self, children: CH);
-    fn is_empty(&self) -> bool;
+    fn options(&self) -> &crate::Options;
 }
 
 fn iter<S: Stack<C, CH>, C: Comparable, CH, F: FnMut(&C, &C) -> CH>(
+    options: &crate::Options,
     old: &BTreeMap<String, C>,
     new: &BTreeMap<String, C>,
     mut next: F,
 ) -> S {
-    let mut stack = S::default();
+    let mut stack = S::new(options);
 
     for (name, r) in new {
         match old.get(name) {
@@ -40,82 +42,532 @@ fn iter<S: Stack<C, CH>, C: Comparable, CH, F: FnMut(&C, &C) -> CH>(
 
 #[derive(Default, Debug)]
 pub struct Diff {
+    options: crate::Options,
+    sizes: BTreeMap<String, crate::lock::Size>,
+    types: BTreeMap<String, String>,
+    server: ForeignServer,
+    new_schema: Schema,
+    extension: Extension,
     schema: Schema,
+    removed_extension: Extension,
+    event_trigger: EventTrigger,
+    publication: Publication,
+    subscription: Subscription,
+    cast: Cast,
+    removed_cast: Cast,
+    foreign_key: Constraint,
+    removed_foreign_key: Constraint,
+    role: Role,
+    membership: Membership,
+    removed_role: Role,
+    setting: Setting,
+    default_privilege: DefaultPrivilege,
 }
 
 impl Diff {
     pub fn from(old: &crate::inspect::Database, new: &crate::inspect::Database) -> Self {
-        let schema = Self::database(old, new);
+        Self::new(old, new, &crate::Options::default())
+    }
+
+    pub fn new(
+        old: &crate::inspect::Database,
+        new: &crate::inspect::Database,
+        options: &crate::Options,
+    ) -> Self {
+        let server = iter(options, &old.servers, &new.servers, |old, new| {
+            if ForeignServer::need_recreate(old, new) {
+                UserMapping::new(options)
+            } else {
+                iter(options, &old.user_mappings, &new.user_mappings, |_, _| {})
+            }
+        });
+        // The foreign tables of a recreated server are dropped with it and
+        // created again
+        let recreated = old
+            .servers
+            .values()
+            .filter(|x| {
+                new.servers
+                    .get(&x.name)
+                    .is_some_and(|new| ForeignServer::need_recreate(x, new))
+            })
+            .map(|x| x.name.as_str())
+            .collect::<Vec<_>>();
+        let new_relation = |old: &crate::inspect::Relation| {
+            new.schemas
+                .get(&old.schema)
+                .and_then(|x| x.relations.get(&format!("{}.{}", old.schema, old.name)))
+        };
+        let rebuilt = |old: &crate::inspect::Relation| {
+            new_relation(old).is_some_and(|new| Relation::need_rebuild(options, old, new))
+        };
+        // The views reading a rebuilt table are recreated with it, the new
+        // ones included, and get their triggers and rules back from their
+        // children
+        let recreated_views = old
+            .schemas
+            .values()
+            .flat_map(|x| x.relations.values())
+            .filter(|x| rebuilt(x))
+            .flat_map(|x| {
+                x.dependents
+                    .iter()
+                    .chain(new_relation(x).into_iter().flat_map(|x| &x.dependents))
+            })
+            .map(|x| format!("{}.{}", x.schema, x.name))
+            .collect::<Vec<_>>();
+        let old_schemas = old
+            .schemas
+            .iter()
+            .map(|(name, x)| {
+                let mut schema = x.clone();
+                schema.relations.retain(|_, x| {
+                    !x.server
+                        .as_deref()
+                        .is_some_and(|server| recreated.contains(&server))
+                });
+
+                for key in &recreated_views {
+                    let Some(view) = new.schemas.get(name).and_then(|x| x.relations.get(key))
+                    else {
+                        continue;
+                    };
+
+                    let relation = schema
+                        .relations
+                        .entry(key.clone())
+                        .or_insert_with(|| view.clone());
+                    relation.triggers.clear();
+                    relation.rules.clear();
+                }
+
+                (name.clone(), schema)
+            })
+            .collect();
+        // Schemas are created before the extensions installed in them
+        let (schemas, new_schemas) = partition(&new.schemas, &old.schemas);
+        let new_schema = iter(options, &BTreeMap::new(), &new_schemas, |old, new| {
+            Self::schema(options, old, new)
+        });
+        let schema = iter(options, &old_schemas, &schemas, |old, new| {
+            Self::schema(options, old, new)
+        });
+        // Extensions are created before the objects using them and dropped after
+        let (extensions, removed_extensions) = partition(&old.extensions, &new.extensions);
+        let extension = iter(options, &extensions, &new.extensions, |_, _| {});
+        let removed_extension = iter(options, &removed_extensions, &BTreeMap::new(), |_, _| {});
+        let event_trigger = iter(options, &old.event_triggers, &new.event_triggers, |_, _| {});
+        let subscription = iter(options, &old.subscriptions, &new.subscriptions, |_, _| {});
+        // Casts are created once their functions exist and dropped before them
+        let (old_casts, new_casts) = if options.extension_members {
+            (old.casts.clone(), new.casts.clone())
+        } else {
+            let own = |casts: &BTreeMap<String, crate::inspect::Cast>| {
+                casts
+                    .iter()
+                    .filter(|(_, x)| x.extension.is_none())
+                    .map(|(name, x)| (name.clone(), x.clone()))
+                    .collect::<BTreeMap<_, _>>()
+            };
+
+            (own(&old.casts), own(&new.casts))
+        };
+        let (casts, removed_casts) = partition(&old_casts, &new_casts);
+        let cast = iter(options, &casts, &new_casts, |_, _| {});
+        let removed_cast = iter(options, &removed_casts, &BTreeMap::new(), |_, _| {});
+        // Foreign keys are dropped before the tables they reference and added
+        // once every table exists, the rebuilt tables lose theirs
+        let is_rebuilt = |name: &str| {
+            old.schemas
+                .values()
+                .flat_map(|x| x.relations.values())
+                .find(|x| format!("{}.{}", x.schema, x.name) == name)
+                .is_some_and(rebuilt)
+        };
+        let new_foreign_keys = foreign_keys(options, new, |_| true);
+        let (foreign_keys, mut removed_foreign_keys) = partition(
+            &foreign_keys(options, old, |x| !rebuilt(x)),
+            &new_foreign_keys,
+        );
+        // The ones referencing a rebuilt table too
+        let (inbound_foreign_keys, foreign_keys) = foreign_keys
+            .into_iter()
+            .partition::<BTreeMap<_, _>, _>(|(_, x)| {
+                x.referenced.as_deref().is_some_and(is_rebuilt)
+            });
+        removed_foreign_keys.extend(inbound_foreign_keys);
+        let foreign_key = iter(options, &foreign_keys, &new_foreign_keys, |_, _| {});
+        let removed_foreign_key = iter(options, &removed_foreign_keys, &BTreeMap::new(), |_, _| {});
+        // A dropped table leaves its publications, a rebuilt one is added back
+        // once recreated
+        let is_dropped = |name: &str| {
+            !new.schemas
+                .values()
+                .flat_map(|x| x.relations.values())
+                .any(|x| format!("{}.{}", x.schema, x.name) == name)
+        };
+        let old_publications = old
+            .publications
+            .iter()
+            .map(|(name, x)| {
+                let mut publication = x.clone();
+                publication
+                    .tables
+                    .retain(|_, x| !is_dropped(&x.relation) && !is_rebuilt(&x.relation));
+
+                (name.clone(), publication)
+            })
+            .collect();
+        let publication = iter(options, &old_publications, &new.publications, |_, _| {});
+        // Roles are created first and dropped once nothing references them
+        let (roles, removed_roles) = partition(&old.roles, &new.roles);
+        let role = iter(options, &roles, &new.roles, |_, _| {});
+        let membership = iter(options, &old.memberships, &new.memberships, |_, _| {});
+        let removed_role = iter(options, &removed_roles, &BTreeMap::new(), |_, _| {});
+        // Settings are applied to the migrated database
+        let new_settings = new
+            .settings
+            .iter()
+            .map(|(name, x)| {
+                let setting = crate::inspect::Setting {
+                    database: old.name.clone(),
+                    ..x.clone()
+                };
+
+                (name.clone(), setting)
+            })
+            .collect();
+        let setting = iter(options, &old.settings, &new_settings, |_, _| {});
+        let default_privilege = iter(
+            options,
+            &old.default_privileges,
+            &new.default_privileges,
+            |_, _| {},
+        );
 
-        Self { schema }
+        Self {
+            options: options.clone(),
+            sizes: Self::sizes(old),
+            types: Self::types(old),
+            server,
+            new_schema,
+            extension,
+            schema,
+            removed_extension,
+            event_trigger,
+            publication,
+            subscription,
+            cast,
+            removed_cast,
+            foreign_key,
+            removed_foreign_key,
+            role,
+            membership,
+            removed_role,
+            setting,
+            default_privilege,
+        }
     }
 
-    fn database(old: &crate::inspect::Database, new: &crate::inspect::Database) -> Schema {
-        iter(&old.schemas, &new.schemas, |old, new| {
-            Self::schema(old, new)
-        })
+    /**
+     * Sizes of the relations to migrate, indexes are mapped to the size of
+     * their table.
+     */
+    fn sizes(database: &crate::inspect::Database) -> BTreeMap<String, crate::lock::Size> {
+        let mut sizes = BTreeMap::new();
+
+        for relation in database.schemas.values().flat_map(|x| x.relations.values()) {
+            let size = crate::lock::Size {
+                relation: format!("{}.{}", relation.schema, relation.name),
+                tuples: relation.tuples,
+                pages: relation.pages,
+            };
+
+            for index in relation.indexes.values() {
+                sizes.insert(index.name.to_ascii_lowercase(), size.clone());
+                sizes.insert(
+                    format!("{}.{}", relation.schema, index.name).to_ascii_lowercase(),
+                    size.clone(),
+                );
+            }
+
+            sizes.insert(size.relation.to_ascii_lowercase(), size);
+        }
+
+        sizes
     }
 
-    fn schema(old: &crate::inspect::Schema, new: &crate::inspect::Schema) -> SchemaComponents {
-        let relation = iter(&old.relations, &new.relations, |old, new| {
-            if old.kind == elephantry::inspect::Kind::OrdinaryTable {
-                Self::relation(old, new)
+    /**
+     * Current types of the columns, keyed by their lowercased
+     * `schema.relation.column` name.
+     */
+    fn types(database: &crate::inspect::Database) -> BTreeMap<String, String> {
+        database
+            .schemas
+            .values()
+            .flat_map(|x| x.relations.values())
+            .flat_map(|x| x.columns.values())
+            .map(|x| {
+                let name = format!("{}.{}.{}", x.parent.schema, x.parent.name, x.name);
+
+                (name.to_ascii_lowercase(), x.ty().to_ascii_lowercase())
+            })
+            .collect()
+    }
+
+    fn schema(
+        options: &crate::Options,
+        old: &crate::inspect::Schema,
+        new: &crate::inspect::Schema,
+    ) -> SchemaComponents {
+        let (old, new) = &if options.extension_members {
+            (old.clone(), new.clone())
+        } else {
+            (
+                without_extension_members(old),
+                without_extension_members(new),
+            )
+        };
+
+        let relation = iter(
+            options,
+            &old.relations,
+            &new.relations,
+            |old, new| match old.kind {
+                elephantry::inspect::Kind::OrdinaryTable => Self::relation(options, old, new),
+                elephantry::inspect::Kind::View => Self::view(options, old, new),
+                _ => RelationComponents::default(),
+            },
+        );
+        let r#enum = iter(options, &old.enums, &new.enums, |_, _| {});
+        let domain = iter(options, &old.domains, &new.domains, |old, new| {
+            if Domain::need_recreate(old, new) {
+                Constraint::default()
             } else {
-                RelationComponents::default()
+                Self::constraint(options, old, new)
             }
         });
-        let r#enum = iter(&old.enums, &new.enums, |_, _| {});
-        let domain = iter(&old.domains, &new.domains, |old, new| {
-            Self::constraint(old, new)
-        });
-        let composite = iter(&old.composites, &new.composites, |_, _| {});
-        let extension = iter(&old.extensions, &new.extensions, |_, _| {});
-        let function = iter(&old.functions, &new.functions, |_, _| {});
-        let trigger = iter(&old.triggers, &new.triggers, |_, _| {});
+        let composite = iter(options, &old.composites, &new.composites, |_, _| {});
+        // Ranges are dropped after the relations using them
+        let (ranges, removed_ranges) = partition(&old.ranges, &new.ranges);
+        let range = iter(options, &ranges, &new.ranges, |_, _| {});
+        let removed_range = iter(options, &removed_ranges, &BTreeMap::new(), |_, _| {});
+        let (functions, removed_functions) = partition(&old.functions, &new.functions);
+        let function = iter(options, &functions, &new.functions, |_, _| {});
+        let removed_function = iter(options, &removed_functions, &BTreeMap::new(), |_, _| {});
+        let (operators, removed_operators) = partition(&old.operators, &new.operators);
+        let operator = iter(options, &operators, &new.operators, |_, _| {});
+        let removed_operator = iter(options, &removed_operators, &BTreeMap::new(), |_, _| {});
+        let (families, removed_families) =
+            partition(&old.operator_families, &new.operator_families);
+        let operator_family = iter(options, &families, &new.operator_families, |_, _| {});
+        let removed_operator_family = iter(options, &removed_families, &BTreeMap::new(), |_, _| {});
+        let (classes, removed_classes) = partition(&old.operator_classes, &new.operator_classes);
+        let operator_class = iter(options, &classes, &new.operator_classes, |_, _| {});
+        let removed_operator_class = iter(options, &removed_classes, &BTreeMap::new(), |_, _| {});
+        // Collations, parsers and dictionaries are dropped once nothing uses them
+        let (collations, removed_collations) = partition(&old.collations, &new.collations);
+        let collation = iter(options, &collations, &new.collations, |_, _| {});
+        let removed_collation = iter(options, &removed_collations, &BTreeMap::new(), |_, _| {});
+        let (parsers, removed_parsers) =
+            partition(&old.text_search_parsers, &new.text_search_parsers);
+        let text_search_parser = iter(options, &parsers, &new.text_search_parsers, |_, _| {});
+        let removed_text_search_parser =
+            iter(options, &removed_parsers, &BTreeMap::new(), |_, _| {});
+        let (dictionaries, removed_dictionaries) =
+            partition(&old.text_search_dictionaries, &new.text_search_dictionaries);
+        let text_search_dictionary = iter(
+            options,
+            &dictionaries,
+            &new.text_search_dictionaries,
+            |_, _| {},
+        );
+        let removed_text_search_dictionary =
+            iter(options, &removed_dictionaries, &BTreeMap::new(), |_, _| {});
+        let text_search_configuration = iter(
+            options,
+            &old.text_search_configurations,
+            &new.text_search_configurations,
+            |_, _| {},
+        );
 
         SchemaComponents {
             relation,
             r#enum,
             domain,
             composite,
-            extension,
             function,
-            trigger,
+            collation,
+            text_search_parser,
+            text_search_dictionary,
+            text_search_configuration,
+            operator,
+            operator_family,
+            operator_class,
+            range,
+            removed_range,
+            removed_collation,
+            removed_text_search_parser,
+            removed_text_search_dictionary,
+            removed_function,
+            removed_operator,
+            removed_operator_family,
+            removed_operator_class,
         }
     }
 
     fn relation(
+        options: &crate::Options,
         old: &crate::inspect::Relation,
         new: &crate::inspect::Relation,
     ) -> RelationComponents {
-        let column = iter(&old.columns, &new.columns, |_, _| {});
-        let constraint = iter(&old.constraints, &new.constraints, |_, _| {});
-        let index = iter(&old.indexes, &new.indexes, |_, _| {});
+        if Relation::need_rebuild(options, old, new) {
+            // The rebuilt table only has its columns, everything else is recreated
+            return RelationComponents {
+                column: Column::new(options),
+                constraint: iter(options, &BTreeMap::new(), &constraints(new), |_, _| {}),
+                index: iter(options, &BTreeMap::new(), &new.indexes, |_, _| {}),
+                trigger: iter(options, &BTreeMap::new(), &new.triggers, |_, _| {}),
+                rule: iter(options, &BTreeMap::new(), &new.rules, |_, _| {}),
+                statistic: iter(options, &BTreeMap::new(), &new.statistics, |_, _| {}),
+            };
+        }
+
+        let mut old_indexes = old.indexes.clone();
+        let mut new_constraints = constraints(new);
+
+        // The index is consumed by the primary key
+        if let Some((name, index)) = primary_key_index(old, new) {
+            old_indexes.remove(name);
+
+            for constraint in new_constraints.values_mut() {
+                if constraint.ty == elephantry::inspect::constraint::Type::PrimaryKey {
+                    constraint.index = Some(index.name.clone());
+                }
+            }
+        }
+
+        let column = iter(options, &old.columns, &new.columns, |_, _| {});
+        let constraint = iter(options, &constraints(old), &new_constraints, |_, _| {});
+        let index = iter(
+            options,
+            &renamed_indexes(&old_indexes, &new.indexes),
+            &new.indexes,
+            |_, _| {},
+        );
+        let trigger = iter(options, &old.triggers, &new.triggers, |_, _| {});
+        let rule = iter(options, &old.rules, &new.rules, |_, _| {});
+        let statistic = iter(options, &old.statistics, &new.statistics, |_, _| {});
 
         RelationComponents {
             column,
             constraint,
             index,
+            trigger,
+            rule,
+            statistic,
+        }
+    }
+
+    fn view(
+        options: &crate::Options,
+        old: &crate::inspect::Relation,
+        new: &crate::inspect::Relation,
+    ) -> RelationComponents {
+        // An updated view is recreated, with all its triggers and rules
+        let (triggers, rules) = if old == new {
+            (old.triggers.clone(), old.rules.clone())
+        } else {
+            (BTreeMap::new(), BTreeMap::new())
+        };
+
+        RelationComponents {
+            trigger: iter(options, &triggers, &new.triggers, |_, _| {}),
+            rule: iter(options, &rules, &new.rules, |_, _| {}),
+            ..Default::default()
         }
     }
 
-    fn constraint(old: &crate::inspect::Domain, new: &crate::inspect::Domain) -> Constraint {
-        iter(&old.constraints, &new.constraints, |_, _| {})
+    fn constraint(
+        options: &crate::Options,
+        old: &crate::inspect::Domain,
+        new: &crate::inspect::Domain,
+    ) -> Constraint {
+        iter(options, &old.constraints, &new.constraints, |_, _| {})
     }
 
     pub fn sql(&self) -> String {
+        let mut script = Script::default();
+        self.role.sql(&mut script);
+        self.membership.sql(&mut script);
+        self.removed_cast.sql(&mut script);
+        self.setting.sql(&mut script);
+        self.removed_foreign_key.sql(&mut script);
+        self.new_schema.sql(&mut script);
+        self.default_privilege.sql(&mut script);
+        self.extension.sql(&mut script);
+        // Foreign data wrappers are created by extensions
+        self.server.sql(&mut script);
+        self.schema.sql(&mut script);
+        self.foreign_key.sql(&mut script);
+        self.removed_extension.sql(&mut script);
+        self.cast.sql(&mut script);
+        self.event_trigger.sql(&mut script);
+        self.publication.sql(&mut script);
+        self.subscription.sql(&mut script);
+        self.removed_role.sql(&mut script);
+
+        if self.options.report {
+            script.sql = crate::lock::annotate(&script.sql, &self.sizes, &self.types);
+            script.after_commit =
+                crate::lock::annotate(&script.after_commit, &self.sizes, &self.types);
+        }
+
         let mut s = String::new();
 
         s.push_str("begin;\n\n");
-        self.schema.sql(&mut s);
+        s.push_str(&script.sql);
         s.push_str("commit;\n");
 
+        if !script.after_commit.is_empty() {
+            s.push('\n');
+            s.push_str(&script.after_commit);
+        }
+
         s
     }
 }
 
+/**
+ * Generated SQL, split between the statements run in the migration
+ * transaction and the ones that must run after it is committed.
+ */
+#[derive(Debug, Default)]
+struct Script {
+    sql: String,
+    after_commit: String,
+}
+
+impl Script {
+    fn push(&mut self, script: impl Into<Script>) {
+        let script = script.into();
+
+        self.sql.push_str(&script.sql);
+        self.after_commit.push_str(&script.after_commit);
+    }
+}
+
+impl From<String> for Script {
+    fn from(sql: String) -> Self {
+        Self {
+            sql,
+            after_commit: String::new(),
+        }
+    }
+}
+
 trait Sql {
-    fn sql(&self, output: &mut dyn std::fmt::Write);
+    fn sql(&self, output: &mut Script);
 }
 
 macro_rules! diff {
@@ -124,6 +576,7 @@ macro_rules! diff {
 
         #[derive(Debug, Default)]
         struct $ty {
+            options: crate::Options,
             added: Vec<$comparable>,
             updated: Vec<($comparable, $comparable)>,
             removed: Vec<$comparable>,
@@ -131,6 +584,13 @@ macro_rules! diff {
         }
 
         impl Stack<$comparable, $child> for $ty {
+            fn new(options: &crate::Options) -> Self {
+                Self {
+                    options: options.clone(),
+                    ..Default::default()
+                }
+            }
+
             fn add(&mut self, new: &$comparable) {
                 self.added.push(new.clone());
             }
@@ -147,35 +607,49 @@ macro_rules! diff {
                 self.children.push(children);
             }
 
-            fn is_empty(&self) -> bool {
-                self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+            fn options(&self) -> &crate::Options {
+                &self.options
             }
         }
 
         impl Sql for $ty {
-            fn sql(&self, output: &mut dyn std::fmt::Write) {
-                if !self.is_empty() {
-                    write!(output, "--\n-- {}\n--\n", stringify!($ty)).ok();
-                }
+            fn sql(&self, output: &mut Script) {
+                let mut script = Script::default();
 
                 for new in &self.added {
-                    output.write_str(&self.sql_added(new)).ok();
+                    script.push(self.sql_added(new));
                 }
 
                 for old in &self.removed {
-                    output.write_str(&self.sql_removed(old)).ok();
+                    script.push(self.sql_removed(old));
                 }
 
                 for (old, new) in &self.updated {
-                    output.write_str(&self.sql_updated(old, new)).ok();
+                    script.push(self.sql_updated(old, new));
+                }
+
+                let header = format!("--\n-- {}\n--\n", stringify!($ty));
+
+                if !script.sql.is_empty() {
+                    output.sql.push_str(&header);
+                    output.sql.push_str(&script.sql);
+                }
+
+                if !script.after_commit.is_empty() {
+                    output.after_commit.push_str(&header);
+                    output.after_commit.push_str(&script.after_commit);
                 }
 
                 for child in &self.children {
                     child.sql(output);
                 }
 
-                if !self.is_empty() {
-                    output.write_str("\n").ok();
+                if !script.sql.is_empty() {
+                    output.sql.push('\n');
+                }
+
+                if !script.after_commit.is_empty() {
+                    output.after_commit.push('\n');
                 }
             }
         }
@@ -185,21 +659,7 @@ macro_rules! diff {
 impl Comparable for () {}
 
 impl Sql for () {
-    fn sql(&self, _: &mut dyn std::fmt::Write) {}
-}
-
-impl Stack<(), ()> for () {
-    fn add(&mut self, _: &()) {}
-
-    fn remove(&mut self, _: &()) {}
-
-    fn update(&mut self, _: &(), _: &()) {}
-
-    fn add_child(&mut self, _: ()) {}
-
-    fn is_empty(&self) -> bool {
-        true
-    }
+    fn sql(&self, _: &mut Script) {}
 }
 
 diff!(Schema, SchemaComponents, crate::inspect::Schema);
@@ -228,26 +688,193 @@ impl Schema {
     }
 }
 
+/**
+ * Splits `old` between the objects still in `new` and the removed ones.
+ */
+fn partition<C: Clone>(
+    old: &BTreeMap<String, C>,
+    new: &BTreeMap<String, C>,
+) -> (BTreeMap<String, C>, BTreeMap<String, C>) {
+    old.iter()
+        .map(|(name, x)| (name.clone(), x.clone()))
+        .partition(|(name, _)| new.contains_key(name))
+}
+
+/**
+ * Constraints of `relation` but its foreign keys, diffed with `Diff::foreign_key`.
+ */
+fn constraints(
+    relation: &crate::inspect::Relation,
+) -> BTreeMap<String, crate::inspect::Constraint> {
+    relation
+        .constraints
+        .iter()
+        .filter(|(_, x)| x.ty != elephantry::inspect::constraint::Type::Foreign)
+        .map(|(name, x)| (name.clone(), x.clone()))
+        .collect()
+}
+
+/**
+ * Rekeys the `old` indexes only differing from a `new` one by their name, so
+ * they are renamed instead of rebuilt.
+ */
+fn renamed_indexes(
+    old: &BTreeMap<String, crate::inspect::Index>,
+    new: &BTreeMap<String, crate::inspect::Index>,
+) -> BTreeMap<String, crate::inspect::Index> {
+    let mut added = new
+        .iter()
+        .filter(|(name, _)| !old.contains_key(*name))
+        .collect::<Vec<_>>();
+    let mut indexes = BTreeMap::new();
+
+    for (name, index) in old {
+        let renamed = if new.contains_key(name) {
+            None
+        } else {
+            added
+                .iter()
+                .position(|(_, x)| x.signature() == index.signature())
+                .map(|x| added.remove(x).0)
+        };
+
+        indexes.insert(renamed.unwrap_or(name).clone(), index.clone());
+    }
+
+    indexes
+}
+
+/**
+ * Valid unique index of `old` on the columns of the new or updated primary
+ * key of `new`, and not kept by `new`.
+ */
+fn primary_key_index<'a>(
+    old: &'a crate::inspect::Relation,
+    new: &crate::inspect::Relation,
+) -> Option<(&'a String, &'a crate::inspect::Index)> {
+    let primary_key = new
+        .constraints
+        .values()
+        .find(|x| x.ty == elephantry::inspect::constraint::Type::PrimaryKey)?;
+
+    if old.constraints.values().any(|x| x == primary_key) {
+        return None;
+    }
+
+    let columns = primary_key.body.strip_prefix("PRIMARY KEY ")?;
+    let structure = format!(" USING btree {columns}");
+
+    old.indexes.iter().find(|(_, x)| {
+        let signature = x.signature();
+
+        x.valid
+            && signature.starts_with("CREATE UNIQUE INDEX ON ")
+            && signature.ends_with(&structure)
+            && !new.indexes.values().any(|y| y.signature() == signature)
+    })
+}
+
+/**
+ * Foreign keys of the `database` tables matching `filter`.
+ */
+fn foreign_keys(
+    options: &crate::Options,
+    database: &crate::inspect::Database,
+    filter: impl Fn(&crate::inspect::Relation) -> bool,
+) -> BTreeMap<String, crate::inspect::Constraint> {
+    database
+        .schemas
+        .values()
+        .flat_map(|x| x.relations.values())
+        .filter(|x| options.extension_members || x.extension.is_none())
+        .filter(|x| filter(x))
+        .flat_map(|x| x.constraints.iter())
+        .filter(|(_, x)| x.ty == elephantry::inspect::constraint::Type::Foreign)
+        .map(|(name, x)| (name.clone(), x.clone()))
+        .collect()
+}
+
+/**
+ * Removes the objects created by an extension, they are managed with it.
+ */
+fn without_extension_members(schema: &crate::inspect::Schema) -> crate::inspect::Schema {
+    let mut schema = schema.clone();
+
+    schema.relations.retain(|_, x| x.extension.is_none());
+    schema.enums.retain(|_, x| x.extension.is_none());
+    schema.domains.retain(|_, x| x.extension.is_none());
+    schema.composites.retain(|_, x| x.extension.is_none());
+    schema.ranges.retain(|_, x| x.extension.is_none());
+    schema.functions.retain(|_, x| x.extension.is_none());
+    schema.collations.retain(|_, x| x.extension.is_none());
+    schema
+        .text_search_parsers
+        .retain(|_, x| x.extension.is_none());
+    schema
+        .text_search_dictionaries
+        .retain(|_, x| x.extension.is_none());
+    schema
+        .text_search_configurations
+        .retain(|_, x| x.extension.is_none());
+    schema.operators.retain(|_, x| x.extension.is_none());
+    schema
+        .operator_families
+        .retain(|_, x| x.extension.is_none());
+    schema.operator_classes.retain(|_, x| x.extension.is_none());
+
+    schema
+}
+
 #[derive(Debug)]
 struct SchemaComponents {
     relation: Relation,
     r#enum: Enum,
     domain: Domain,
     composite: Composite,
-    extension: Extension,
     function: Function,
-    trigger: Trigger,
+    collation: Collation,
+    text_search_parser: TextSearchParser,
+    text_search_dictionary: TextSearchDictionary,
+    text_search_configuration: TextSearchConfiguration,
+    removed_collation: Collation,
+    removed_text_search_parser: TextSearchParser,
+    removed_text_search_dictionary: TextSearchDictionary,
+    operator: Operator,
+    operator_family: OperatorFamily,
+    operator_class: OperatorClass,
+    removed_function: Function,
+    removed_operator: Operator,
+    removed_operator_family: OperatorFamily,
+    removed_operator_class: OperatorClass,
+    range: Range,
+    removed_range: Range,
 }
 
 impl Sql for &SchemaComponents {
-    fn sql(&self, output: &mut dyn std::fmt::Write) {
+    fn sql(&self, output: &mut Script) {
+        // Columns and indexes may use collations and text search configurations
+        self.collation.sql(output);
+        self.text_search_parser.sql(output);
+        self.text_search_dictionary.sql(output);
+        self.text_search_configuration.sql(output);
+        // Functions first, triggers are created with their relation
+        self.function.sql(output);
+        self.operator.sql(output);
+        self.operator_family.sql(output);
+        self.operator_class.sql(output);
+        self.range.sql(output);
         self.relation.sql(output);
         self.r#enum.sql(output);
         self.domain.sql(output);
         self.composite.sql(output);
-        self.extension.sql(output);
-        self.function.sql(output);
-        self.trigger.sql(output);
+        self.removed_range.sql(output);
+        self.removed_operator_class.sql(output);
+        self.removed_operator_family.sql(output);
+        self.removed_operator.sql(output);
+        self.removed_text_search_dictionary.sql(output);
+        self.removed_text_search_parser.sql(output);
+        self.removed_collation.sql(output);
+        self.removed_function.sql(output);
     }
 }
 
@@ -256,13 +883,19 @@ struct RelationComponents {
     column: Column,
     constraint: Constraint,
     index: Index,
+    trigger: Trigger,
+    rule: Rule,
+    statistic: Statistic,
 }
 
 impl Sql for &RelationComponents {
-    fn sql(&self, output: &mut dyn std::fmt::Write) {
+    fn sql(&self, output: &mut Script) {
         self.column.sql(output);
         self.constraint.sql(output);
         self.index.sql(output);
+        self.trigger.sql(output);
+        self.rule.sql(output);
+        self.statistic.sql(output);
     }
 }
 
@@ -272,27 +905,47 @@ impl Relation {
     fn sql_added(&self, new: &crate::inspect::Relation) -> String {
         use elephantry::inspect::Kind::*;
 
-        match new.kind {
+        let mut sql = match new.kind {
             OrdinaryTable => self.create_table(new),
             View | MaterializedView => self.create_view(new),
-            _ => String::new(),
+            ForeignTable => self.create_foreign_table(new),
+            _ => return String::new(),
+        };
+
+        // A new relation has no children, its triggers and statistics are
+        // created with it
+        let trigger = Trigger::new(self.options());
+
+        for new in new.triggers.values() {
+            sql.push_str(&trigger.sql_added(new));
+        }
+
+        let statistic = Statistic::new(self.options());
+
+        for new in new.statistics.values() {
+            sql.push_str(&statistic.sql_added(new));
         }
+
+        sql
     }
 
-    fn create_table(&self, new: &crate::inspect::Relation) -> String {
+    fn persistence(relation: &crate::inspect::Relation) -> &'static str {
         use elephantry::inspect::Persistence;
 
-        let mut sql = String::from("create");
-
-        match new.persistence {
-            Persistence::Permanent => (),
-            Persistence::Unlogged => sql.push_str(" unlogged"),
-            Persistence::Temporary => sql.push_str(" temporary"),
+        match relation.persistence {
+            Persistence::Permanent => "",
+            Persistence::Unlogged => " unlogged",
+            Persistence::Temporary => " temporary",
         }
+    }
+
+    fn create_table(&self, new: &crate::inspect::Relation) -> String {
+        let mut sql = format!("create{} table {}(", Self::persistence(new), new.fullname());
 
-        sql.push_str(&format!(" table {}(", new.fullname()));
+        let mut columns = new.columns.values().collect::<Vec<_>>();
+        columns.sort_by_key(|x| x.position);
 
-        for column in new.columns.values() {
+        for column in columns {
             sql.push_str(&format!("\n    {} {}", column.name, column.ty()));
             if column.is_primary {
                 sql.push_str(" primary key");
@@ -302,7 +955,10 @@ impl Relation {
 
         sql = sql.trim_end_matches(',').to_string();
 
-        sql.push_str("\n);\n");
+        sql.push_str(&format!(
+            "\n){};\n",
+            storage(&new.parameters, new.tablespace.as_deref())
+        ));
 
         let comment = comment("table", &new.fullname(), None, new.comment.as_deref());
         sql.push_str(&comment);
@@ -310,6 +966,38 @@ impl Relation {
         sql
     }
 
+    fn create_foreign_table(&self, new: &crate::inspect::Relation) -> String {
+        let mut columns = new.columns.values().collect::<Vec<_>>();
+        columns.sort_by_key(|x| x.position);
+
+        let columns = columns
+            .iter()
+            .map(|x| format!("\n    \"{}\" {}", x.name, x.ty()))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let mut sql = format!(
+            "create foreign table {}({columns}\n) server \"{}\"{};\n",
+            new.fullname(),
+            new.server.as_deref().unwrap_or_default(),
+            options(&new.options),
+        );
+        sql.push_str(&secrets(
+            &format!("foreign table {}", new.fullname()),
+            &new.options,
+        ));
+
+        let comment = comment(
+            "foreign table",
+            &new.fullname(),
+            None,
+            new.comment.as_deref(),
+        );
+        sql.push_str(&comment);
+
+        sql
+    }
+
     fn create_view(&self, new: &crate::inspect::Relation) -> String {
         if let Some(definition) = &new.definition {
             format!("create {} {} as {definition}\n", new.kind, new.fullname())
@@ -319,6 +1007,11 @@ impl Relation {
     }
 
     fn sql_removed(&self, old: &crate::inspect::Relation) -> String {
+        if old.kind == elephantry::inspect::Kind::ForeignTable {
+            // Already dropped if its server was removed
+            return format!("drop foreign table if exists {};\n", old.fullname());
+        }
+
         format!("drop {} {};\n", old.kind, old.fullname())
     }
 
@@ -329,6 +1022,62 @@ impl Relation {
     ) -> String {
         let mut sql = String::new();
 
+        if Self::need_rebuild(self.options(), old, new) {
+            return self.rebuild(old, new);
+        }
+
+        if old.kind == elephantry::inspect::Kind::OrdinaryTable && Self::is_reordered(old, new) {
+            let columns = new
+                .column_names()
+                .iter()
+                .map(|x| format!("\"{x}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            sql.push_str(&format!(
+                "-- warning: column order of {} differs, expected {columns}\n",
+                old.fullname()
+            ));
+        }
+
+        if old.kind == elephantry::inspect::Kind::ForeignTable {
+            if old.server != new.server || old.columns != new.columns {
+                sql.push_str(&self.sql_removed(old));
+                sql.push_str(&self.sql_added(new));
+
+                return sql;
+            }
+
+            sql.push_str(&alter_options(
+                &format!("foreign table {}", old.fullname()),
+                &old.options,
+                &new.options,
+            ));
+        }
+
+        if matches!(
+            old.kind,
+            elephantry::inspect::Kind::OrdinaryTable | elephantry::inspect::Kind::MaterializedView
+        ) {
+            let object = format!("{} {}", old.kind, old.fullname());
+
+            if old.persistence != new.persistence {
+                sql.push_str(&format!(
+                    "alter {object} set{};\n",
+                    match Self::persistence(new) {
+                        "" => " logged",
+                        persistence => persistence,
+                    }
+                ));
+            }
+
+            sql.push_str(&alter_storage(
+                &object,
+                (&old.parameters, old.tablespace.as_deref()),
+                (&new.parameters, new.tablespace.as_deref()),
+            ));
+        }
+
         if old.kind == elephantry::inspect::Kind::View {
             sql.push_str(&self.sql_removed(old));
             if let Some(definition) = &new.definition {
@@ -347,106 +1096,503 @@ impl Relation {
     }
 }
 
-diff!(Enum, (), crate::inspect::Enum);
+impl Relation {
+    fn need_rebuild(
+        options: &crate::Options,
+        old: &crate::inspect::Relation,
+        new: &crate::inspect::Relation,
+    ) -> bool {
+        options.rebuild
+            && old.kind == elephantry::inspect::Kind::OrdinaryTable
+            && Self::is_reordered(old, new)
+    }
 
-impl Enum {
-    fn sql_added(&self, new: &crate::inspect::Enum) -> String {
-        let elements = new
-            .elements
-            .iter()
-            .map(|x| format!("'{x}'"))
-            .collect::<Vec<_>>()
-            .join(", ");
+    /**
+     * Columns kept from `old` can’t be moved, and added ones go at the end,
+     * like the recreated ones.
+     */
+    fn is_reordered(old: &crate::inspect::Relation, new: &crate::inspect::Relation) -> bool {
+        let kept = |name: &str| {
+            let old = old.columns.values().find(|x| x.name == name);
+            let new = new.columns.values().find(|x| x.name == name);
+
+            match (old, new) {
+                (Some(old), Some(new)) => !Column::is_recreated(old, new),
+                _ => false,
+            }
+        };
 
-        format!("create type {} as enum({elements});\n", new.fullname())
-    }
+        let new_columns = new.column_names();
 
-    fn sql_removed(&self, old: &crate::inspect::Enum) -> String {
-        format!("drop type {};\n", old.fullname())
+        let mut expected = old
+            .column_names()
+            .into_iter()
+            .filter(|x| kept(x))
+            .collect::<Vec<_>>();
+        expected.extend(new_columns.iter().filter(|x| !kept(x)));
+
+        expected != new_columns
     }
 
-    fn sql_updated(&self, old: &crate::inspect::Enum, new: &crate::inspect::Enum) -> String {
+    /**
+     * Recreates the table with the columns of `new`, in its order, and
+     * copies the data. Constraints and indexes are added back by the
+     * children, the foreign keys referencing it and its publications by
+     * `Diff`. Its owner, privileges and policies are kept.
+     *
+     * The views reading it are recreated with their comment, and the owner,
+     * privileges and materialized view indexes of the previous ones. Their
+     * triggers and rules are added back by their children.
+     */
+    fn rebuild(&self, old: &crate::inspect::Relation, new: &crate::inspect::Relation) -> String {
+        let previous = format!("{}__pgdiff_rebuild", old.name);
+
         let mut sql = String::new();
 
-        let old_elements = &old.elements;
-        let new_elements = &new.elements;
+        // The views would follow the renamed table
+        let dependents = old.dependents.iter().chain(
+            new.dependents
+                .iter()
+                .filter(|x| !old.dependents.iter().any(|y| y.fullname() == x.fullname())),
+        );
 
-        for old_element in old_elements {
-            if !new_elements.contains(old_element) {
-                sql.push_str(&format!(
-                    "delete from pg_enum e using pg_type t, pg_namespace n where e.enumtypid = t.oid and t.typname = '{}' and t.typnamespace = n.oid and n.nspname = '{}' and enumlabel = '{old_element}';\n",
-                    new.name,
-                    new.parent.name,
-                ));
-            }
+        for dependent in dependents.rev() {
+            sql.push_str(&format!(
+                "drop {} if exists {};\n",
+                dependent.kind,
+                dependent.fullname()
+            ));
         }
 
-        for (x, new_element) in new_elements.iter().enumerate() {
-            if !old_elements.contains(new_element) {
-                if let Some(after) = new_elements.get(x - 1) {
-                    sql.push_str(&format!(
-                        "alter type {} add value '{new_element}' after '{after}';\n",
-                        new.fullname()
-                    ));
-                } else if let Some(before) = new_elements.get(x + 1) {
-                    sql.push_str(&format!(
-                        "alter type {} add value '{new_element}' before '{before}';\n",
-                        new.fullname()
-                    ));
-                } else {
-                    sql.push_str(&format!(
-                        "alter type {} add value '{new_element}';\n",
-                        new.fullname()
-                    ));
-                }
-            }
-        }
+        sql.push_str(&format!(
+            "alter table {} rename to \"{previous}\";\n",
+            old.fullname()
+        ));
 
-        sql
-    }
-}
+        let mut columns = new.columns.values().collect::<Vec<_>>();
+        columns.sort_by_key(|x| x.position);
 
-diff!(Domain, Constraint, crate::inspect::Domain);
+        let definitions = columns
+            .iter()
+            .map(|x| {
+                let mut definition = format!("\n    \"{}\" {}", x.name, x.ty());
+                if let Some(collation) = &x.collation {
+                    definition.push_str(&format!(" collate {collation}"));
+                }
+                if let Some(default) = &x.default {
+                    definition.push_str(&format!(" default {default}"));
+                }
+                definition.push_str(&generation(x));
+                if x.is_notnull {
+                    definition.push_str(" not null");
+                }
+                definition
+            })
+            .collect::<Vec<_>>()
+            .join(",");
 
-impl Domain {
-    fn sql_added(&self, new: &crate::inspect::Domain) -> String {
-        let mut sql = format!("create domain {} as {}", new.fullname(), new.ty);
+        sql.push_str(&format!(
+            "create{} table {}({definitions}\n){};\n",
+            Self::persistence(new),
+            new.fullname(),
+            storage(&new.parameters, new.tablespace.as_deref())
+        ));
 
-        for constraint in new.constraints.values() {
-            sql.push_str(&format!(" {}", constraint.definition));
-        }
+        let mut targets = Vec::new();
+        let mut values = Vec::new();
 
-        sql.push_str(";\n");
+        for column in &columns {
+            let Some(current) = old.columns.values().find(|x| x.name == column.name) else {
+                continue;
+            };
 
-        sql
-    }
+            if column.generated.is_some() {
+                continue;
+            }
 
-    fn sql_removed(&self, old: &crate::inspect::Domain) -> String {
-        format!("drop domain {};\n", old.fullname())
-    }
+            let key = format!("{}.{}.{}", new.schema, new.name, column.name);
 
-    fn sql_updated(&self, old: &crate::inspect::Domain, new: &crate::inspect::Domain) -> String {
-        let mut sql = String::new();
+            let value = match self.options().using.get(&key) {
+                Some(using) => using.clone(),
+                None if current.ty() == column.ty() => format!("\"{}\"", column.name),
+                None => format!("\"{}\"::{}", column.name, column.ty()),
+            };
 
-        if old.is_notnull != new.is_notnull {
-            if new.is_notnull {
+            targets.push(format!("\"{}\"", column.name));
+            values.push(value);
+        }
+
+        let overriding = if columns.iter().any(|x| {
+            x.identity
+                .as_ref()
+                .is_some_and(|x| x.generation == "always")
+        }) {
+            " overriding system value"
+        } else {
+            ""
+        };
+
+        sql.push_str(&format!(
+            "insert into {}({}){overriding} select {} from \"{}\".\"{previous}\";\n",
+            new.fullname(),
+            targets.join(", "),
+            values.join(", "),
+            old.schema,
+        ));
+
+        // A sequence must have the owner of the table it’s linked to
+        if let Some(owner) = &old.owner {
+            sql.push_str(&format!(
+                "alter table {} owner to \"{owner}\";\n",
+                new.fullname()
+            ));
+        }
+
+        // The `serial` sequences would be dropped with the previous table
+        for column in &columns {
+            let sequence = old
+                .columns
+                .values()
+                .find(|x| x.name == column.name)
+                .and_then(|x| x.sequence.as_ref());
+
+            if let Some(sequence) = sequence.filter(|_| column.sequence.is_some()) {
+                sql.push_str(&format!(
+                    "alter sequence {sequence} owned by {};\n",
+                    column.fullname()
+                ));
+            }
+        }
+
+        sql.push_str(&format!("drop table \"{}\".\"{previous}\";\n", old.schema));
+
+        // The new identity sequences start over
+        for column in columns.iter().filter(|x| x.identity.is_some()) {
+            if !old.columns.values().any(|x| x.name == column.name) {
+                continue;
+            }
+
+            sql.push_str(&format!(
+                "select setval(pg_get_serial_sequence('{}', '{}'), coalesce(max(\"{}\"), 0) + 1, false) from {};\n",
+                new.fullname().replace('\'', "''"),
+                column.name.replace('\'', "''"),
+                column.name,
+                new.fullname(),
+            ));
+        }
+
+        sql.push_str(&comment(
+            "table",
+            &new.fullname(),
+            None,
+            new.comment.as_deref(),
+        ));
+
+        for column in &columns {
+            let alter = format!(
+                "alter table {} alter column \"{}\"",
+                new.fullname(),
+                column.name
+            );
+
+            if column.statistics != -1 {
+                sql.push_str(&format!("{alter} set statistics {};\n", column.statistics));
+            }
+
+            if column.storage != column.default_storage {
+                sql.push_str(&format!("{alter} set storage {};\n", column.storage));
+            }
+
+            if let Some(compression) = &column.compression {
+                sql.push_str(&format!("{alter} set compression {compression};\n"));
+            }
+        }
+
+        for column in columns {
+            sql.push_str(&comment(
+                "column",
+                &column.fullname(),
+                None,
+                column.comment.as_deref(),
+            ));
+        }
+
+        sql.push_str(&Self::security(old, &new.fullname()));
+
+        for dependent in &new.dependents {
+            sql.push_str(&format!(
+                "create {} {} as {}\n",
+                dependent.kind,
+                dependent.fullname(),
+                dependent.definition
+            ));
+            sql.push_str(&comment(
+                &dependent.kind.to_string(),
+                &dependent.fullname(),
+                None,
+                dependent.comment.as_deref(),
+            ));
+
+            let Some(previous) = old
+                .dependents
+                .iter()
+                .find(|x| x.fullname() == dependent.fullname())
+            else {
+                continue;
+            };
+
+            sql.push_str(&format!(
+                "alter {} {} owner to \"{}\";\n",
+                dependent.kind,
+                dependent.fullname(),
+                previous.owner
+            ));
+            sql.push_str(&grants(&previous.privileges, &dependent.fullname()));
+
+            for index in &previous.indexes {
+                sql.push_str(&format!("{index};\n"));
+            }
+        }
+
+        sql
+    }
+
+    /**
+     * Privileges and row security of `relation`, applied to `table`.
+     */
+    fn security(relation: &crate::inspect::Relation, table: &str) -> String {
+        let mut sql = grants(&relation.privileges, table);
+
+        if relation.row_security {
+            sql.push_str(&format!("alter table {table} enable row level security;\n"));
+        }
+
+        if relation.force_row_security {
+            sql.push_str(&format!("alter table {table} force row level security;\n"));
+        }
+
+        for policy in &relation.policies {
+            sql.push_str(&format!(
+                "create policy \"{}\" on {table} as {} for {} to {}",
+                policy.name,
+                if policy.permissive {
+                    "permissive"
+                } else {
+                    "restrictive"
+                },
+                policy.command,
+                policy.roles.join(", "),
+            ));
+
+            if let Some(using) = &policy.using {
+                sql.push_str(&format!(" using ({using})"));
+            }
+
+            if let Some(check) = &policy.check {
+                sql.push_str(&format!(" with check ({check})"));
+            }
+
+            sql.push_str(";\n");
+        }
+
+        sql
+    }
+}
+
+diff!(Enum, (), crate::inspect::Enum);
+
+impl Enum {
+    fn sql_added(&self, new: &crate::inspect::Enum) -> String {
+        let elements = new
+            .elements
+            .iter()
+            .map(|x| format!("'{x}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("create type {} as enum({elements});\n", new.fullname())
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Enum) -> String {
+        format!("drop type {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Enum, new: &crate::inspect::Enum) -> String {
+        let mut sql = String::new();
+
+        let old_elements = &old.elements;
+        let new_elements = &new.elements;
+
+        for old_element in old_elements {
+            if !new_elements.contains(old_element) {
+                sql.push_str(&format!(
+                    "delete from pg_enum e using pg_type t, pg_namespace n where e.enumtypid = t.oid and t.typname = '{}' and t.typnamespace = n.oid and n.nspname = '{}' and enumlabel = '{old_element}';\n",
+                    new.name,
+                    new.parent.name,
+                ));
+            }
+        }
+
+        for (x, new_element) in new_elements.iter().enumerate() {
+            if !old_elements.contains(new_element) {
+                if let Some(after) = new_elements.get(x - 1) {
+                    sql.push_str(&format!(
+                        "alter type {} add value '{new_element}' after '{after}';\n",
+                        new.fullname()
+                    ));
+                } else if let Some(before) = new_elements.get(x + 1) {
+                    sql.push_str(&format!(
+                        "alter type {} add value '{new_element}' before '{before}';\n",
+                        new.fullname()
+                    ));
+                } else {
+                    sql.push_str(&format!(
+                        "alter type {} add value '{new_element}';\n",
+                        new.fullname()
+                    ));
+                }
+            }
+        }
+
+        sql
+    }
+}
+
+diff!(Domain, Constraint, crate::inspect::Domain);
+
+impl Domain {
+    fn sql_added(&self, new: &crate::inspect::Domain) -> String {
+        let mut sql = format!("create domain {} as {}", new.fullname(), new.base_type);
+
+        if let Some(collation) = &new.collation {
+            sql.push_str(&format!(" collate {collation}"));
+        }
+
+        if let Some(default) = &new.default {
+            sql.push_str(&format!(" default {default}"));
+        }
+
+        if new.is_notnull {
+            sql.push_str(" not null");
+        }
+
+        let (constraints, not_valid) = new
+            .constraints
+            .values()
+            .partition::<Vec<_>, _>(|x| x.validated);
+
+        for constraint in constraints {
+            sql.push_str(&format!(
+                " constraint \"{}\" {}",
+                constraint.name, constraint.definition
+            ));
+        }
+
+        sql.push_str(";\n");
+
+        // `create domain` doesn’t accept `not valid`
+        for constraint in not_valid {
+            sql.push_str(&format!(
+                "alter domain {} add constraint \"{}\" {};\n",
+                new.fullname(),
+                constraint.name,
+                constraint.definition
+            ));
+        }
+
+        let comment = comment("domain", &new.fullname(), None, new.description.as_deref());
+        sql.push_str(&comment);
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Domain) -> String {
+        format!("drop domain {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Domain, new: &crate::inspect::Domain) -> String {
+        if Self::need_recreate(old, new) {
+            return self.recreate(old, new);
+        }
+
+        let mut sql = String::new();
+
+        if old.is_notnull != new.is_notnull {
+            if new.is_notnull {
                 sql.push_str(&format!("alter domain {} set not null;\n", new.fullname()));
             } else {
                 sql.push_str(&format!("alter domain {} drop not null;\n", new.fullname()));
             }
         }
 
-        match (&old.default, &new.default) {
-            (None, None) => (),
-            (_, Some(default)) => sql.push_str(&format!(
-                "alter domain {} set default {default};\n",
-                new.fullname()
-            )),
-            (Some(_), None) => {
-                sql.push_str(&format!("alter domain {} drop default;\n", new.fullname()))
+        if old.default != new.default {
+            match &new.default {
+                Some(default) => sql.push_str(&format!(
+                    "alter domain {} set default {default};\n",
+                    new.fullname()
+                )),
+                None => sql.push_str(&format!("alter domain {} drop default;\n", new.fullname())),
             }
         }
 
+        if old.owner != new.owner {
+            sql.push_str(&format!(
+                "alter domain {} owner to \"{}\";\n",
+                new.fullname(),
+                new.owner
+            ));
+        }
+
+        sql.push_str(&comment(
+            "domain",
+            &new.fullname(),
+            old.description.as_deref(),
+            new.description.as_deref(),
+        ));
+
+        sql
+    }
+
+    /**
+     * The base type and the collation of a domain can’t be altered, it’s
+     * recreated.
+     */
+    fn need_recreate(old: &crate::inspect::Domain, new: &crate::inspect::Domain) -> bool {
+        old.base_type != new.base_type || old.collation != new.collation
+    }
+
+    /**
+     * The table columns of the domain are switched to its previous base type
+     * while it’s recreated. Other dependencies, like functions, make the
+     * `drop domain` fail.
+     */
+    fn recreate(&self, old: &crate::inspect::Domain, new: &crate::inspect::Domain) -> String {
+        let mut sql = String::new();
+
+        // The relations are updated first, the columns are the ones of `new`
+        for column in &new.columns {
+            sql.push_str(&format!(
+                "alter table {} alter column \"{}\" type {};\n",
+                column.table_fullname(),
+                column.name,
+                old.base_type
+            ));
+        }
+
+        sql.push_str(&self.sql_removed(old));
+        sql.push_str(&self.sql_added(new));
+
+        for column in &new.columns {
+            sql.push_str(&format!(
+                "alter table {} alter column \"{}\" type {} using \"{}\"::{};\n",
+                column.table_fullname(),
+                column.name,
+                new.fullname(),
+                column.name,
+                new.fullname()
+            ));
+        }
+
         sql
     }
 }
@@ -487,17 +1633,71 @@ impl Composite {
     }
 }
 
+diff!(Range, (), crate::inspect::Range);
+
+impl Range {
+    fn sql_added(&self, new: &crate::inspect::Range) -> String {
+        let mut options = vec![format!("subtype = {}", new.subtype)];
+
+        let optionals = [
+            ("subtype_opclass", &new.subtype_opclass),
+            ("collation", &new.collation),
+            ("canonical", &new.canonical),
+            ("subtype_diff", &new.subtype_diff),
+            ("multirange_type_name", &new.multirange),
+        ];
+
+        for (name, value) in optionals {
+            if let Some(value) = value {
+                options.push(format!("{name} = {value}"));
+            }
+        }
+
+        format!(
+            "create type {} as range ({});\n",
+            new.fullname(),
+            options.join(", ")
+        )
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Range) -> String {
+        format!("drop type {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Range, new: &crate::inspect::Range) -> String {
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
+    }
+}
+
 diff!(Column, (), crate::inspect::Column);
 
 impl Column {
     fn sql_added(&self, new: &crate::inspect::Column) -> String {
+        let collation = new
+            .collation
+            .as_ref()
+            .map(|x| format!(" collate {x}"))
+            .unwrap_or_default();
+
         let mut sql = format!(
-            "alter table {} add column \"{}\" {};\n",
+            "alter table {} add column \"{}\" {}{collation}{};\n",
             new.parent.fullname(),
             new.name,
-            new.ty()
+            new.ty(),
+            generation(new),
         );
 
+        if new.storage != new.default_storage {
+            sql.push_str(&self.alter_storage(new));
+        }
+
+        if new.compression.is_some() {
+            sql.push_str(&self.alter_compression(new));
+        }
+
         let comment = comment("column", &new.fullname(), None, new.comment.as_deref());
         sql.push_str(&comment);
 
@@ -512,7 +1712,20 @@ impl Column {
         )
     }
 
-    fn sql_updated(&self, old: &crate::inspect::Column, new: &crate::inspect::Column) -> String {
+    fn sql_updated(&self, old: &crate::inspect::Column, new: &crate::inspect::Column) -> Script {
+        let mut script = Script::default();
+
+        if Self::is_recreated(old, new) {
+            script.sql = format!(
+                "-- warning: recreating generated column {}\n{}{}",
+                old.fullname(),
+                self.sql_removed(old),
+                self.sql_added(new)
+            );
+
+            return script;
+        }
+
         let mut sql = match (&old.default, &new.default) {
             (_, Some(default)) => format!(
                 "alter table {} alter column \"{}\" set default {default};\n",
@@ -536,7 +1749,30 @@ impl Column {
         sql.push_str(&comment);
 
         if old.is_notnull != new.is_notnull {
-            if new.is_notnull {
+            if new.is_notnull && self.options().online {
+                // A validated check constraint lets `set not null` skip the table scan
+                let constraint =
+                    identifier(&format!("pgdiff_not_null_{}_{}", old.position, old.name));
+
+                sql.push_str(&format!(
+                    "alter table {} add constraint \"{constraint}\" check (\"{}\" is not null) not valid;\n",
+                    old.parent.fullname(),
+                    old.name
+                ));
+                script.after_commit.push_str(&format!(
+                    "alter table {} validate constraint \"{constraint}\";\n",
+                    old.parent.fullname()
+                ));
+                script.after_commit.push_str(&format!(
+                    "alter table {} alter column \"{}\" set not null;\n",
+                    old.parent.fullname(),
+                    old.name
+                ));
+                script.after_commit.push_str(&format!(
+                    "alter table {} drop constraint \"{constraint}\";\n",
+                    old.parent.fullname()
+                ));
+            } else if new.is_notnull {
                 sql.push_str(&format!(
                     "alter table {} alter column \"{}\" set not null;\n",
                     old.parent.fullname(),
@@ -551,41 +1787,208 @@ impl Column {
             }
         }
 
-        if old.ty() != new.ty() {
+        if old.generated.is_some() && new.generated.is_none() {
             sql.push_str(&format!(
-                "alter table {} alter column \"{}\" type {} using \"{}\"::{};\n",
+                "alter table {} alter column \"{}\" drop expression;\n",
                 old.parent.fullname(),
-                old.name,
-                new.ty(),
-                old.name,
-                new.ty(),
+                old.name
             ));
         }
 
-        sql
-    }
-}
+        if old.identity != new.identity {
+            sql.push_str(&self.alter_identity(old, new));
+        }
 
-diff!(Extension, (), crate::inspect::Extension);
+        if old.ty() != new.ty() || old.collation != new.collation {
+            sql.push_str(&self.sql_retype(old, new));
+        }
 
-impl Extension {
-    fn sql_added(&self, new: &crate::inspect::Extension) -> String {
-        format!("create extension \"{}\";\n", new.name)
-    }
+        // A new type comes with its default storage
+        let storage = if old.ty() == new.ty() {
+            old.storage != new.storage
+        } else {
+            new.storage != new.default_storage
+        };
 
-    fn sql_removed(&self, old: &crate::inspect::Extension) -> String {
-        format!("drop extension \"{}\";\n", old.name)
-    }
+        if storage {
+            sql.push_str(&self.alter_storage(new));
+        }
+
+        if old.compression != new.compression {
+            sql.push_str(&self.alter_compression(new));
+        }
+
+        if old.statistics != new.statistics {
+            sql.push_str(&format!(
+                "alter table {} alter column \"{}\" set statistics {};\n",
+                old.parent.fullname(),
+                old.name,
+                new.statistics
+            ));
+        }
+
+        script.sql = sql;
+
+        script
+    }
+
+    /**
+     * Only a stored expression can be dropped, other generation changes
+     * recreate the column, at the end of the table.
+     */
+    fn is_recreated(old: &crate::inspect::Column, new: &crate::inspect::Column) -> bool {
+        old.generated != new.generated && new.generated.is_some()
+    }
+
+    fn sql_retype(&self, old: &crate::inspect::Column, new: &crate::inspect::Column) -> String {
+        use crate::conversion::Conversion;
+
+        let mut sql = String::new();
+        let (old_ty, new_ty) = (old.ty(), new.ty());
+
+        if crate::conversion::is_lossy(&old_ty, &new_ty) {
+            sql.push_str(&format!(
+                "-- warning: converting {} from {old_ty} to {new_ty} may lose data\n",
+                old.fullname()
+            ));
+        }
+
+        let key = format!("{}.{}.{}", old.parent.schema, old.parent.name, old.name);
+
+        let using = match self.options().using.get(&key) {
+            _ if old_ty == new_ty => String::new(),
+            Some(using) => format!(" using {using}"),
+            None => match crate::conversion::classify(&old_ty, &new_ty) {
+                Conversion::BinaryCoercible | Conversion::Widening => String::new(),
+                Conversion::Cast => format!(" using \"{}\"::{new_ty}", old.name),
+            },
+        };
+
+        // Changing the type also resets the collation
+        let collation = match &new.collation {
+            Some(collation) => format!(" collate {collation}"),
+            None if old.collation.is_some() && old_ty == new_ty => {
+                " collate \"default\"".to_string()
+            }
+            None => String::new(),
+        };
+
+        sql.push_str(&format!(
+            "alter table {} alter column \"{}\" type {new_ty}{collation}{using};\n",
+            old.parent.fullname(),
+            old.name,
+        ));
+
+        sql
+    }
+
+    fn alter_identity(&self, old: &crate::inspect::Column, new: &crate::inspect::Column) -> String {
+        let alter = format!(
+            "alter table {} alter column \"{}\"",
+            old.parent.fullname(),
+            old.name
+        );
+
+        let (old_identity, new_identity) = match (&old.identity, &new.identity) {
+            (None, None) => return String::new(),
+            (Some(_), None) => return format!("{alter} drop identity;\n"),
+            (None, Some(_)) => return format!("{alter} add{};\n", generation(new)),
+            (Some(old), Some(new)) => (old, new),
+        };
+
+        let mut changes = Vec::new();
+
+        if old_identity.generation != new_identity.generation {
+            changes.push(format!("set generated {}", new_identity.generation));
+        }
+
+        for (name, old, new) in [
+            ("start with", old_identity.start, new_identity.start),
+            (
+                "increment by",
+                old_identity.increment,
+                new_identity.increment,
+            ),
+            ("minvalue", old_identity.min, new_identity.min),
+            ("maxvalue", old_identity.max, new_identity.max),
+            ("cache", old_identity.cache, new_identity.cache),
+        ] {
+            if old != new {
+                changes.push(format!("set {name} {new}"));
+            }
+        }
+
+        if old_identity.cycle != new_identity.cycle {
+            let cycle = if new_identity.cycle {
+                "cycle"
+            } else {
+                "no cycle"
+            };
+            changes.push(format!("set {cycle}"));
+        }
+
+        format!("{alter} {};\n", changes.join(" "))
+    }
+
+    fn alter_storage(&self, column: &crate::inspect::Column) -> String {
+        format!(
+            "alter table {} alter column \"{}\" set storage {};\n",
+            column.parent.fullname(),
+            column.name,
+            column.storage
+        )
+    }
+
+    fn alter_compression(&self, column: &crate::inspect::Column) -> String {
+        format!(
+            "alter table {} alter column \"{}\" set compression {};\n",
+            column.parent.fullname(),
+            column.name,
+            column.compression.as_deref().unwrap_or("default")
+        )
+    }
+}
+
+diff!(Extension, (), crate::inspect::Extension);
+
+impl Extension {
+    fn sql_added(&self, new: &crate::inspect::Extension) -> String {
+        format!(
+            "create extension {} schema \"{}\" version '{}';\n",
+            new.fullname(),
+            new.schema,
+            new.version
+        )
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Extension) -> String {
+        format!("drop extension {};\n", old.fullname())
+    }
 
     fn sql_updated(
         &self,
         old: &crate::inspect::Extension,
         new: &crate::inspect::Extension,
     ) -> String {
-        format!(
-            "alter extension \"{}\" update to '{}';\n",
-            old.name, new.version
-        )
+        let mut sql = String::new();
+
+        if old.schema != new.schema {
+            sql.push_str(&format!(
+                "alter extension {} set schema \"{}\";\n",
+                new.fullname(),
+                new.schema
+            ));
+        }
+
+        if old.version != new.version {
+            sql.push_str(&format!(
+                "alter extension {} update to '{}';\n",
+                new.fullname(),
+                new.version
+            ));
+        }
+
+        sql
     }
 }
 
@@ -614,86 +2017,1663 @@ impl Function {
     }
 }
 
-diff!(Trigger, (), crate::inspect::Trigger);
+diff!(Operator, (), crate::inspect::Operator);
+
+impl Operator {
+    fn sql_added(&self, new: &crate::inspect::Operator) -> String {
+        let mut options = vec![format!("function = {}", new.function)];
+
+        if let Some(left) = &new.left {
+            options.push(format!("leftarg = {left}"));
+        }
+
+        options.push(format!("rightarg = {}", new.right));
+
+        let optionals = [
+            ("commutator", &new.commutator),
+            ("negator", &new.negator),
+            ("restrict", &new.restrict),
+            ("join", &new.join),
+        ];
+
+        for (name, value) in optionals {
+            if let Some(value) = value {
+                options.push(format!("{name} = {value}"));
+            }
+        }
+
+        if new.hashes {
+            options.push("hashes".to_string());
+        }
+
+        if new.merges {
+            options.push("merges".to_string());
+        }
 
-impl Trigger {
-    fn sql_added(&self, new: &crate::inspect::Trigger) -> String {
         format!(
-            "create or replace trigger \"{}\" {} {} on \"{}\".\"{}\" for each {} {};\n",
-            new.name,
-            new.timing,
-            new.event,
-            new.parent.fullname(),
-            new.table,
-            new.orientation,
-            new.action,
+            "create operator {} ({});\n",
+            new.fullname(),
+            options.join(", ")
         )
     }
 
-    fn sql_removed(&self, old: &crate::inspect::Trigger) -> String {
+    fn sql_removed(&self, old: &crate::inspect::Operator) -> String {
+        format!("drop operator \"{}\".{};\n", old.schema, old.signature())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::Operator,
+        new: &crate::inspect::Operator,
+    ) -> String {
+        let estimators = crate::inspect::Operator {
+            restrict: new.restrict.clone(),
+            join: new.join.clone(),
+            ..old.clone()
+        };
+
+        // Only the selectivity estimators can be altered
+        if &estimators != new {
+            let mut sql = self.sql_removed(old);
+            sql.push_str(&self.sql_added(new));
+
+            return sql;
+        }
+
         format!(
-            "drop trigger \"{}\" on \"{}\".\"{}\";\n",
-            old.name,
-            old.parent.fullname(),
-            old.table
+            "alter operator \"{}\".{} set (restrict = {}, join = {});\n",
+            new.schema,
+            new.signature(),
+            new.restrict.as_deref().unwrap_or("none"),
+            new.join.as_deref().unwrap_or("none"),
         )
     }
+}
+
+diff!(OperatorFamily, (), crate::inspect::OperatorFamily);
+
+impl OperatorFamily {
+    fn sql_added(&self, new: &crate::inspect::OperatorFamily) -> String {
+        format!("create operator family {};\n", new.fullname())
+    }
 
-    fn sql_updated(&self, _: &crate::inspect::Trigger, new: &crate::inspect::Trigger) -> String {
-        self.sql_added(new)
+    fn sql_removed(&self, old: &crate::inspect::OperatorFamily) -> String {
+        format!("drop operator family {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::OperatorFamily,
+        new: &crate::inspect::OperatorFamily,
+    ) -> String {
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
     }
 }
 
-diff!(Constraint, (), crate::inspect::Constraint);
+diff!(OperatorClass, (), crate::inspect::OperatorClass);
+
+impl OperatorClass {
+    fn sql_added(&self, new: &crate::inspect::OperatorClass) -> String {
+        let default = if new.default { " default" } else { "" };
+
+        let mut items = new
+            .operators
+            .iter()
+            .chain(&new.functions)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if let Some(storage) = &new.storage {
+            items.push(format!("storage {storage}"));
+        }
 
-impl Constraint {
-    fn sql_added(&self, new: &crate::inspect::Constraint) -> String {
         format!(
-            "alter {} {} add constraint \"{}\" {};\n",
-            new.parent_type, new.parent_name, new.name, new.definition
+            "create operator class \"{}\".\"{}\"{default} for type {} using {} family {} as\n    {};\n",
+            new.schema,
+            new.name,
+            new.ty,
+            new.method,
+            new.family,
+            items.join(",\n    ")
         )
     }
 
-    fn sql_removed(&self, old: &crate::inspect::Constraint) -> String {
+    fn sql_removed(&self, old: &crate::inspect::OperatorClass) -> String {
+        format!("drop operator class {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::OperatorClass,
+        new: &crate::inspect::OperatorClass,
+    ) -> String {
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
+    }
+}
+
+diff!(Cast, (), crate::inspect::Cast);
+
+impl Cast {
+    fn sql_added(&self, new: &crate::inspect::Cast) -> String {
+        let method = match &new.function {
+            Some(function) => format!("with function {function}"),
+            None if new.inout => "with inout".to_string(),
+            None => "without function".to_string(),
+        };
+
+        let context = new
+            .context
+            .as_ref()
+            .map(|x| format!(" as {x}"))
+            .unwrap_or_default();
+
+        format!("create cast {} {method}{context};\n", new.fullname())
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Cast) -> String {
+        format!("drop cast {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Cast, new: &crate::inspect::Cast) -> String {
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
+    }
+}
+
+diff!(Collation, (), crate::inspect::Collation);
+
+impl Collation {
+    fn sql_added(&self, new: &crate::inspect::Collation) -> String {
+        let mut options = vec![format!("provider = {}", new.provider)];
+
+        if new.provider == "libc" {
+            options.push(format!(
+                "lc_collate = {}",
+                literal(new.lc_collate.as_deref().unwrap_or_default())
+            ));
+            options.push(format!(
+                "lc_ctype = {}",
+                literal(new.lc_ctype.as_deref().unwrap_or_default())
+            ));
+        } else {
+            options.push(format!(
+                "locale = {}",
+                literal(new.locale.as_deref().unwrap_or_default())
+            ));
+        }
+
+        if !new.deterministic {
+            options.push("deterministic = false".to_string());
+        }
+
         format!(
-            "alter {} {} drop constraint \"{}\";\n",
-            old.parent_type, old.parent_name, old.name
+            "create collation {} ({});\n",
+            new.fullname(),
+            options.join(", ")
         )
     }
 
+    fn sql_removed(&self, old: &crate::inspect::Collation) -> String {
+        format!("drop collation {};\n", old.fullname())
+    }
+
     fn sql_updated(
         &self,
-        old: &crate::inspect::Constraint,
-        new: &crate::inspect::Constraint,
+        old: &crate::inspect::Collation,
+        new: &crate::inspect::Collation,
     ) -> String {
-        let mut sql = String::new();
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
 
-        sql.push_str(&self.sql_removed(old));
+        sql
+    }
+}
+
+diff!(TextSearchParser, (), crate::inspect::TextSearchParser);
+
+impl TextSearchParser {
+    fn sql_added(&self, new: &crate::inspect::TextSearchParser) -> String {
+        let headline = new
+            .headline
+            .as_ref()
+            .map(|x| format!(", headline = {x}"))
+            .unwrap_or_default();
+
+        format!(
+            "create text search parser {} (start = {}, gettoken = {}, end = {}, lextypes = {}{headline});\n",
+            new.fullname(),
+            new.start,
+            new.token,
+            new.end,
+            new.lextypes,
+        )
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::TextSearchParser) -> String {
+        format!("drop text search parser {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::TextSearchParser,
+        new: &crate::inspect::TextSearchParser,
+    ) -> String {
+        let mut sql = self.sql_removed(old);
         sql.push_str(&self.sql_added(new));
 
         sql
     }
 }
 
-diff!(Index, (), crate::inspect::Index);
+diff!(
+    TextSearchDictionary,
+    (),
+    crate::inspect::TextSearchDictionary
+);
 
-impl Index {
-    fn sql_added(&self, new: &crate::inspect::Index) -> String {
-        format!("{};\n", new.definition)
+impl TextSearchDictionary {
+    fn sql_added(&self, new: &crate::inspect::TextSearchDictionary) -> String {
+        let options = new
+            .options
+            .as_ref()
+            .map(|x| format!(", {x}"))
+            .unwrap_or_default();
+
+        format!(
+            "create text search dictionary {} (template = {}{options});\n",
+            new.fullname(),
+            new.template,
+        )
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::TextSearchDictionary) -> String {
+        format!("drop text search dictionary {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::TextSearchDictionary,
+        new: &crate::inspect::TextSearchDictionary,
+    ) -> String {
+        if old.template != new.template {
+            let mut sql = self.sql_removed(old);
+            sql.push_str(&self.sql_added(new));
+
+            return sql;
+        }
+
+        let old_options = Self::options(old);
+        let new_options = Self::options(new);
+
+        let mut options = new_options.clone();
+        // An option without value is reset
+        options.extend(
+            old_options
+                .iter()
+                .filter(|x| !new_options.iter().any(|y| Self::key(x) == Self::key(y)))
+                .map(|x| Self::key(x)),
+        );
+
+        format!(
+            "alter text search dictionary {} ({});\n",
+            new.fullname(),
+            options.join(", ")
+        )
+    }
+
+    /**
+     * Splits the options on the commas outside of quoted values.
+     */
+    fn options(dictionary: &crate::inspect::TextSearchDictionary) -> Vec<&str> {
+        let Some(options) = &dictionary.options else {
+            return Vec::new();
+        };
+
+        let mut list = Vec::new();
+        let mut quoted = false;
+        let mut start = 0;
+
+        for (x, c) in options.char_indices() {
+            match c {
+                '\'' => quoted = !quoted,
+                ',' if !quoted => {
+                    list.push(options[start..x].trim());
+                    start = x + 1;
+                }
+                _ => (),
+            }
+        }
+
+        list.push(options[start..].trim());
+
+        list
+    }
+
+    fn key(option: &str) -> &str {
+        option.split_once('=').map_or(option, |(key, _)| key.trim())
+    }
+}
+
+diff!(
+    TextSearchConfiguration,
+    (),
+    crate::inspect::TextSearchConfiguration
+);
+
+impl TextSearchConfiguration {
+    /**
+     * `copy = …` isn’t recorded in the catalog, the configuration is created
+     * from its parser with all its mappings instead, which gives the same
+     * result.
+     */
+    fn sql_added(&self, new: &crate::inspect::TextSearchConfiguration) -> String {
+        let mut sql = format!(
+            "create text search configuration {} (parser = {});\n",
+            new.fullname(),
+            new.parser
+        );
+
+        sql.push_str(&Self::mappings(new, "add", &new.mappings));
+
+        sql
     }
 
-    fn sql_removed(&self, old: &crate::inspect::Index) -> String {
-        format!("drop index {};\n", old.name)
+    fn sql_removed(&self, old: &crate::inspect::TextSearchConfiguration) -> String {
+        format!("drop text search configuration {};\n", old.fullname())
     }
 
-    fn sql_updated(&self, old: &crate::inspect::Index, new: &crate::inspect::Index) -> String {
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::TextSearchConfiguration,
+        new: &crate::inspect::TextSearchConfiguration,
+    ) -> String {
+        if old.parser != new.parser {
+            let mut sql = self.sql_removed(old);
+            sql.push_str(&self.sql_added(new));
+
+            return sql;
+        }
+
+        let mut added = BTreeMap::new();
+        let mut altered = BTreeMap::new();
+
+        for (token, dictionaries) in &new.mappings {
+            match old.mappings.get(token) {
+                None => {
+                    added.insert(token.clone(), dictionaries.clone());
+                }
+                Some(old) if old != dictionaries => {
+                    altered.insert(token.clone(), dictionaries.clone());
+                }
+                Some(_) => (),
+            }
+        }
+
+        let removed = old
+            .mappings
+            .keys()
+            .filter(|x| !new.mappings.contains_key(*x))
+            .map(|x| x.as_str())
+            .collect::<Vec<_>>();
+
         let mut sql = String::new();
 
-        sql.push_str(&self.sql_removed(old));
-        sql.push_str(&self.sql_added(new));
+        if !removed.is_empty() {
+            sql.push_str(&format!(
+                "alter text search configuration {} drop mapping for {};\n",
+                new.fullname(),
+                removed.join(", ")
+            ));
+        }
+
+        sql.push_str(&Self::mappings(new, "add", &added));
+        sql.push_str(&Self::mappings(new, "alter", &altered));
 
         sql
     }
+
+    /**
+     * Groups the tokens mapped to the same dictionaries in one statement.
+     */
+    fn mappings(
+        configuration: &crate::inspect::TextSearchConfiguration,
+        action: &str,
+        mappings: &BTreeMap<String, Vec<String>>,
+    ) -> String {
+        let mut tokens = BTreeMap::<&Vec<String>, Vec<&str>>::new();
+
+        for (token, dictionaries) in mappings {
+            tokens.entry(dictionaries).or_default().push(token);
+        }
+
+        tokens
+            .iter()
+            .map(|(dictionaries, tokens)| {
+                format!(
+                    "alter text search configuration {} {action} mapping for {} with {};\n",
+                    configuration.fullname(),
+                    tokens.join(", "),
+                    dictionaries.join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+diff!(Trigger, (), crate::inspect::Trigger);
+
+impl Trigger {
+    fn sql_added(&self, new: &crate::inspect::Trigger) -> String {
+        let mut sql = format!("{};\n", new.definition);
+
+        if new.state != crate::inspect::TriggerState::Origin {
+            sql.push_str(&self.alter_state(new));
+        }
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Trigger) -> String {
+        format!("drop trigger {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Trigger, new: &crate::inspect::Trigger) -> String {
+        if old.definition == new.definition {
+            return self.alter_state(new);
+        }
+
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
+    }
+
+    fn alter_state(&self, trigger: &crate::inspect::Trigger) -> String {
+        format!(
+            "alter table {} {} trigger \"{}\";\n",
+            trigger.parent.fullname(),
+            trigger.state,
+            trigger.name
+        )
+    }
+}
+
+diff!(ForeignServer, UserMapping, crate::inspect::ForeignServer);
+
+impl ForeignServer {
+    fn sql_added(&self, new: &crate::inspect::ForeignServer) -> String {
+        let mut sql = format!("create server {}", new.fullname());
+
+        if let Some(ty) = &new.ty {
+            sql.push_str(&format!(" type {}", literal(ty)));
+        }
+
+        if let Some(version) = &new.version {
+            sql.push_str(&format!(" version {}", literal(version)));
+        }
+
+        sql.push_str(&format!(
+            " foreign data wrapper \"{}\"{};\n",
+            new.wrapper,
+            options(&new.options)
+        ));
+        sql.push_str(&secrets(
+            &format!("server {}", new.fullname()),
+            &new.options,
+        ));
+
+        for user_mapping in new.user_mappings.values() {
+            sql.push_str(&UserMapping::create(user_mapping));
+        }
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::ForeignServer) -> String {
+        // Its user mappings and foreign tables can’t outlive it
+        format!("drop server {} cascade;\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::ForeignServer,
+        new: &crate::inspect::ForeignServer,
+    ) -> String {
+        if Self::need_recreate(old, new) {
+            let mut sql = self.sql_removed(old);
+            sql.push_str(&self.sql_added(new));
+
+            return sql;
+        }
+
+        let mut sql = String::new();
+
+        if old.version != new.version {
+            let version = new.version.as_deref().map_or("null".to_string(), literal);
+
+            sql.push_str(&format!(
+                "alter server {} version {version};\n",
+                old.fullname()
+            ));
+        }
+
+        sql.push_str(&alter_options(
+            &format!("server {}", old.fullname()),
+            &old.options,
+            &new.options,
+        ));
+
+        sql
+    }
+}
+
+impl ForeignServer {
+    fn need_recreate(
+        old: &crate::inspect::ForeignServer,
+        new: &crate::inspect::ForeignServer,
+    ) -> bool {
+        old.wrapper != new.wrapper || old.ty != new.ty
+    }
+}
+
+diff!(UserMapping, (), crate::inspect::UserMapping);
+
+impl UserMapping {
+    fn create(user_mapping: &crate::inspect::UserMapping) -> String {
+        let mut sql = format!(
+            "create user mapping {}{};\n",
+            user_mapping.fullname(),
+            options(&user_mapping.options)
+        );
+        sql.push_str(&secrets(
+            &format!("user mapping {}", user_mapping.fullname()),
+            &user_mapping.options,
+        ));
+
+        sql
+    }
+
+    fn sql_added(&self, new: &crate::inspect::UserMapping) -> String {
+        Self::create(new)
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::UserMapping) -> String {
+        format!("drop user mapping {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::UserMapping,
+        new: &crate::inspect::UserMapping,
+    ) -> String {
+        alter_options(
+            &format!("user mapping {}", old.fullname()),
+            &old.options,
+            &new.options,
+        )
+    }
+}
+
+diff!(Publication, (), crate::inspect::Publication);
+
+impl Publication {
+    fn sql_added(&self, new: &crate::inspect::Publication) -> String {
+        let mut sql = format!("create publication {}", new.fullname());
+
+        if new.all_tables {
+            sql.push_str(" for all tables");
+        } else if !new.tables.is_empty() {
+            sql.push_str(&format!(" for {}", Self::tables(new.tables.values())));
+        }
+
+        sql.push_str(&format!(" with ({});\n", Self::parameters(new)));
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Publication) -> String {
+        format!("drop publication {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::Publication,
+        new: &crate::inspect::Publication,
+    ) -> String {
+        if old.all_tables != new.all_tables {
+            let mut sql = self.sql_removed(old);
+            sql.push_str(&self.sql_added(new));
+
+            return sql;
+        }
+
+        let mut sql = String::new();
+
+        if Self::parameters(old) != Self::parameters(new) {
+            sql.push_str(&format!(
+                "alter publication {} set ({});\n",
+                old.fullname(),
+                Self::parameters(new)
+            ));
+        }
+
+        let updated = new
+            .tables
+            .iter()
+            .any(|(name, table)| old.tables.get(name).is_some_and(|x| x != table));
+
+        if updated {
+            // Column lists and row filters can only be replaced with the whole list
+            sql.push_str(&format!(
+                "alter publication {} set {};\n",
+                old.fullname(),
+                Self::tables(new.tables.values())
+            ));
+
+            return sql;
+        }
+
+        let added = new
+            .tables
+            .iter()
+            .filter(|(name, _)| !old.tables.contains_key(*name))
+            .map(|(_, table)| table)
+            .collect::<Vec<_>>();
+
+        if !added.is_empty() {
+            sql.push_str(&format!(
+                "alter publication {} add {};\n",
+                old.fullname(),
+                Self::tables(added.into_iter())
+            ));
+        }
+
+        let removed = old
+            .tables
+            .keys()
+            .filter(|name| !new.tables.contains_key(*name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !removed.is_empty() {
+            sql.push_str(&format!(
+                "alter publication {} drop table {};\n",
+                old.fullname(),
+                removed.join(", ")
+            ));
+        }
+
+        sql
+    }
+
+    fn tables<'a>(tables: impl Iterator<Item = &'a crate::inspect::PublicationTable>) -> String {
+        let tables = tables
+            .map(|table| {
+                let mut sql = table.name.clone();
+
+                if let Some(columns) = &table.columns {
+                    let columns = columns
+                        .iter()
+                        .map(|x| format!("\"{x}\""))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    sql.push_str(&format!(" ({columns})"));
+                }
+
+                if let Some(filter) = &table.filter {
+                    sql.push_str(&format!(" where ({filter})"));
+                }
+
+                sql
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("table {tables}")
+    }
+
+    fn parameters(publication: &crate::inspect::Publication) -> String {
+        let publish = [
+            (publication.insert, "insert"),
+            (publication.update, "update"),
+            (publication.delete, "delete"),
+            (publication.truncate, "truncate"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, operation)| *operation)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+        format!(
+            "publish = '{publish}', publish_via_partition_root = {}",
+            publication.via_root
+        )
+    }
+}
+
+diff!(Subscription, (), crate::inspect::Subscription);
+
+/**
+ * Subscriptions can’t be created nor dropped in a transaction block, they are
+ * managed after the migration commit.
+ */
+impl Subscription {
+    fn sql_added(&self, new: &crate::inspect::Subscription) -> Script {
+        let connection = new
+            .connection
+            .as_deref()
+            .map_or("********".to_string(), Self::connection);
+
+        let mut sql = format!(
+            "create subscription {} connection {} publication {}",
+            new.fullname(),
+            literal(&connection),
+            Self::publications(&new.publications)
+        );
+
+        if !new.enabled {
+            sql.push_str(" with (connect = false)");
+        }
+
+        sql.push_str(";\n");
+
+        let Some(connection) = &new.connection else {
+            sql = format!(
+                "-- warning: connection of subscription {} can’t be read, create it with:\n-- {sql}",
+                new.fullname()
+            );
+
+            return Script {
+                sql: String::new(),
+                after_commit: sql,
+            };
+        };
+
+        sql.push_str(&Self::password(new, connection));
+
+        Script {
+            sql: String::new(),
+            after_commit: sql,
+        }
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Subscription) -> Script {
+        Script {
+            sql: String::new(),
+            after_commit: format!("drop subscription {};\n", old.fullname()),
+        }
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::Subscription,
+        new: &crate::inspect::Subscription,
+    ) -> Script {
+        let mut sql = String::new();
+
+        // Unreadable connections aren’t compared
+        match (&old.connection, &new.connection) {
+            (Some(old_connection), Some(connection)) if old_connection != connection => {
+                sql.push_str(&format!(
+                    "alter subscription {} connection {};\n",
+                    old.fullname(),
+                    literal(&Self::connection(connection))
+                ));
+                sql.push_str(&Self::password(new, connection));
+            }
+            _ => (),
+        }
+
+        if old.enabled && !new.enabled {
+            sql.push_str(&format!("alter subscription {} disable;\n", old.fullname()));
+        }
+
+        if old.publications != new.publications {
+            // Disabled subscriptions can’t be refreshed
+            let refresh = if new.enabled {
+                ""
+            } else {
+                " with (refresh = false)"
+            };
+
+            sql.push_str(&format!(
+                "alter subscription {} set publication {}{refresh};\n",
+                old.fullname(),
+                Self::publications(&new.publications)
+            ));
+        }
+
+        if !old.enabled && new.enabled {
+            sql.push_str(&format!("alter subscription {} enable;\n", old.fullname()));
+        }
+
+        Script {
+            sql: String::new(),
+            after_commit: sql,
+        }
+    }
+
+    /**
+     * Connection string without its password, see `password`.
+     */
+    fn connection(connection: &str) -> String {
+        connection
+            .split_whitespace()
+            .filter(|x| !x.starts_with("password="))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /**
+     * Passwords aren’t copied, a commented-out statement is left to set them
+     * by hand.
+     */
+    fn password(subscription: &crate::inspect::Subscription, connection: &str) -> String {
+        if !connection
+            .split_whitespace()
+            .any(|x| x.starts_with("password="))
+        {
+            return String::new();
+        }
+
+        let connection = format!("{} password=********", Self::connection(connection));
+
+        format!(
+            "-- warning: password of subscription {} isn’t copied, set it with:\n-- alter subscription {} connection {};\n",
+            subscription.fullname(),
+            subscription.fullname(),
+            literal(connection.trim_start()),
+        )
+    }
+
+    fn publications(publications: &[String]) -> String {
+        publications
+            .iter()
+            .map(|x| format!("\"{x}\""))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+diff!(Role, (), crate::inspect::Role);
+
+impl Role {
+    fn sql_added(&self, new: &crate::inspect::Role) -> String {
+        let mut sql = format!("create role {}", new.fullname());
+        let attributes = Self::attributes(None, new);
+
+        if !attributes.is_empty() {
+            sql.push_str(&format!(" with {}", attributes.join(" ")));
+        }
+
+        sql.push_str(";\n");
+        sql.push_str(&alter_settings(
+            &format!("role {}", new.fullname()),
+            &[],
+            &new.settings,
+        ));
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Role) -> String {
+        format!("drop role {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Role, new: &crate::inspect::Role) -> String {
+        let mut sql = String::new();
+        let attributes = Self::attributes(Some(old), new);
+
+        if !attributes.is_empty() {
+            sql.push_str(&format!(
+                "alter role {} with {};\n",
+                new.fullname(),
+                attributes.join(" ")
+            ));
+        }
+
+        sql.push_str(&alter_settings(
+            &format!("role {}", new.fullname()),
+            &old.settings,
+            &new.settings,
+        ));
+
+        sql
+    }
+
+    /**
+     * Attributes differing from `old`, or from the `create role` defaults.
+     */
+    fn attributes(old: Option<&crate::inspect::Role>, new: &crate::inspect::Role) -> Vec<String> {
+        let mut attributes = Vec::new();
+
+        let flags = [
+            ("login", old.is_some_and(|x| x.login), new.login),
+            ("inherit", old.is_none_or(|x| x.inherit), new.inherit),
+            ("createdb", old.is_some_and(|x| x.createdb), new.createdb),
+            (
+                "createrole",
+                old.is_some_and(|x| x.createrole),
+                new.createrole,
+            ),
+        ];
+
+        for (name, old, new) in flags {
+            if old != new {
+                let prefix = if new { "" } else { "no" };
+                attributes.push(format!("{prefix}{name}"));
+            }
+        }
+
+        if old.map_or(-1, |x| x.connection_limit) != new.connection_limit {
+            attributes.push(format!("connection limit {}", new.connection_limit));
+        }
+
+        if old.and_then(|x| x.valid_until.as_deref()) != new.valid_until.as_deref() {
+            attributes.push(format!(
+                "valid until {}",
+                literal(new.valid_until.as_deref().unwrap_or("infinity"))
+            ));
+        }
+
+        attributes
+    }
+}
+
+diff!(Membership, (), crate::inspect::Membership);
+
+impl Membership {
+    fn sql_added(&self, new: &crate::inspect::Membership) -> String {
+        format!("grant \"{}\" to \"{}\";\n", new.role, new.member)
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Membership) -> String {
+        format!("revoke \"{}\" from \"{}\";\n", old.role, old.member)
+    }
+
+    fn sql_updated(
+        &self,
+        _: &crate::inspect::Membership,
+        _: &crate::inspect::Membership,
+    ) -> String {
+        String::new()
+    }
+}
+
+diff!(Setting, (), crate::inspect::Setting);
+
+impl Setting {
+    fn sql_added(&self, new: &crate::inspect::Setting) -> String {
+        alter_settings(&new.fullname(), &[], &new.settings)
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Setting) -> String {
+        alter_settings(&old.fullname(), &old.settings, &[])
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Setting, new: &crate::inspect::Setting) -> String {
+        alter_settings(&new.fullname(), &old.settings, &new.settings)
+    }
+}
+
+diff!(DefaultPrivilege, (), crate::inspect::DefaultPrivilege);
+
+/**
+ * Without `pg_default_acl` entry, a grantee has its default privileges.
+ */
+impl DefaultPrivilege {
+    fn sql_added(&self, new: &crate::inspect::DefaultPrivilege) -> String {
+        Self::alter(new, &new.defaults, &new.privileges)
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::DefaultPrivilege) -> String {
+        Self::alter(old, &old.privileges, &old.defaults)
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::DefaultPrivilege,
+        new: &crate::inspect::DefaultPrivilege,
+    ) -> String {
+        Self::alter(new, &old.privileges, &new.privileges)
+    }
+
+    fn alter(
+        privilege: &crate::inspect::DefaultPrivilege,
+        old: &[String],
+        new: &[String],
+    ) -> String {
+        let mut sql = String::new();
+
+        let grant = new
+            .iter()
+            .filter(|x| !old.contains(x))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !grant.is_empty() {
+            sql.push_str(&format!(
+                "alter default privileges {} grant {} on {} to {};\n",
+                privilege.target(),
+                grant.join(", "),
+                privilege.ty,
+                privilege.grantee()
+            ));
+        }
+
+        let revoke = old
+            .iter()
+            .filter(|x| !new.contains(x))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !revoke.is_empty() {
+            sql.push_str(&format!(
+                "alter default privileges {} revoke {} on {} from {};\n",
+                privilege.target(),
+                revoke.join(", "),
+                privilege.ty,
+                privilege.grantee()
+            ));
+        }
+
+        sql
+    }
+}
+
+diff!(EventTrigger, (), crate::inspect::EventTrigger);
+
+impl EventTrigger {
+    fn sql_added(&self, new: &crate::inspect::EventTrigger) -> String {
+        let mut sql = format!("create event trigger {} on {}", new.fullname(), new.event);
+
+        if let Some(tags) = &new.tags {
+            let tags = tags
+                .iter()
+                .map(|x| format!("'{x}'"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            sql.push_str(&format!(" when tag in ({tags})"));
+        }
+
+        sql.push_str(&format!(" execute function {}();\n", new.function));
+
+        if new.state != crate::inspect::TriggerState::Origin {
+            sql.push_str(&self.alter_state(new));
+        }
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::EventTrigger) -> String {
+        format!("drop event trigger {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::EventTrigger,
+        new: &crate::inspect::EventTrigger,
+    ) -> String {
+        if old.event == new.event && old.tags == new.tags && old.function == new.function {
+            return self.alter_state(new);
+        }
+
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
+    }
+
+    fn alter_state(&self, event_trigger: &crate::inspect::EventTrigger) -> String {
+        format!(
+            "alter event trigger {} {};\n",
+            event_trigger.fullname(),
+            event_trigger.state
+        )
+    }
+}
+
+diff!(Statistic, (), crate::inspect::Statistic);
+
+impl Statistic {
+    fn sql_added(&self, new: &crate::inspect::Statistic) -> String {
+        let mut sql = format!("{};\n", new.definition);
+
+        if new.target != -1 {
+            sql.push_str(&self.alter_target(new));
+        }
+
+        sql
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Statistic) -> String {
+        format!("drop statistics {};\n", old.fullname())
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::Statistic,
+        new: &crate::inspect::Statistic,
+    ) -> String {
+        if old.definition == new.definition {
+            return self.alter_target(new);
+        }
+
+        let mut sql = self.sql_removed(old);
+        sql.push_str(&self.sql_added(new));
+
+        sql
+    }
+
+    fn alter_target(&self, statistic: &crate::inspect::Statistic) -> String {
+        format!(
+            "alter statistics {} set statistics {};\n",
+            statistic.fullname(),
+            statistic.target
+        )
+    }
+}
+
+diff!(Rule, (), crate::inspect::Rule);
+
+impl Rule {
+    fn sql_added(&self, new: &crate::inspect::Rule) -> String {
+        format!(
+            "{}\n",
+            new.definition
+                .replacen("CREATE RULE", "CREATE OR REPLACE RULE", 1)
+        )
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Rule) -> String {
+        format!("drop rule {};\n", old.fullname())
+    }
+
+    fn sql_updated(&self, _: &crate::inspect::Rule, new: &crate::inspect::Rule) -> String {
+        self.sql_added(new)
+    }
+}
+
+diff!(Constraint, (), crate::inspect::Constraint);
+
+impl Constraint {
+    fn sql_added(&self, new: &crate::inspect::Constraint) -> Script {
+        use elephantry::inspect::constraint::Type;
+
+        if new.ty == Type::PrimaryKey
+            && let Some(script) = self.primary_key_using_index(None, new)
+        {
+            return script;
+        }
+
+        let not_valid = new.validated
+            && match new.ty {
+                Type::Check => new.parent_type == "domain" || self.options().online,
+                Type::Foreign => self.options().online,
+                _ => false,
+            };
+
+        if !not_valid {
+            return format!(
+                "alter {} {} add constraint \"{}\" {};\n",
+                new.parent_type, new.parent_name, new.name, new.definition
+            )
+            .into();
+        }
+
+        let mut script = Script::from(format!(
+            "alter {} {} add constraint \"{}\" {} not valid;\n",
+            new.parent_type, new.parent_name, new.name, new.definition
+        ));
+        script.push(self.validate(new));
+
+        script
+    }
+
+    /**
+     * Attaches the primary key to an existing unique index, or in online
+     * mode to one built concurrently after the commit. The `old` primary key
+     * is dropped in the same statement, the table is never left without one.
+     */
+    fn primary_key_using_index(
+        &self,
+        old: Option<&crate::inspect::Constraint>,
+        new: &crate::inspect::Constraint,
+    ) -> Option<Script> {
+        let columns = new.body.strip_prefix("PRIMARY KEY ")?;
+
+        if new.deferrable || columns.contains(" USING INDEX ") {
+            return None;
+        }
+
+        let drop = old
+            .map(|x| format!(" drop constraint \"{}\",", x.name))
+            .unwrap_or_default();
+        let attach = |index: &str| {
+            format!(
+                "alter {} {}{drop} add constraint \"{}\" primary key using index \"{index}\";\n",
+                new.parent_type, new.parent_name, new.name
+            )
+        };
+
+        if let Some(index) = &new.index {
+            return Some(attach(index).into());
+        }
+
+        if !self.options().online {
+            return None;
+        }
+
+        // The index is renamed after the constraint once attached
+        let index = identifier(&format!("pgdiff_{}", new.name));
+        let sql = format!(
+            "create unique index concurrently \"{index}\" on {} {columns};\n{}",
+            new.parent_name,
+            attach(&index)
+        );
+
+        Some(Script {
+            after_commit: sql,
+            ..Default::default()
+        })
+    }
+
+    /**
+     * The validation scans the table, it’s delayed after the commit in
+     * online mode.
+     */
+    fn validate(&self, new: &crate::inspect::Constraint) -> Script {
+        let validate = format!(
+            "alter {} {} validate constraint \"{}\";\n",
+            new.parent_type, new.parent_name, new.name
+        );
+
+        if self.options().online {
+            Script {
+                after_commit: validate,
+                ..Default::default()
+            }
+        } else {
+            validate.into()
+        }
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Constraint) -> String {
+        format!(
+            "alter {} {} drop constraint \"{}\";\n",
+            old.parent_type, old.parent_name, old.name
+        )
+    }
+
+    fn sql_updated(
+        &self,
+        old: &crate::inspect::Constraint,
+        new: &crate::inspect::Constraint,
+    ) -> Script {
+        use elephantry::inspect::constraint::Type;
+
+        let mut script = Script::default();
+        let deferrability = old.deferrable != new.deferrable || old.deferred != new.deferred;
+
+        // Only foreign keys can change their deferrability in place
+        if old.name != new.name
+            || old.ty != new.ty
+            || old.body != new.body
+            || old.no_inherit != new.no_inherit
+            || (deferrability && new.ty != Type::Foreign)
+        {
+            if old.ty == Type::PrimaryKey
+                && new.ty == Type::PrimaryKey
+                && let Some(script) = self.primary_key_using_index(Some(old), new)
+            {
+                return script;
+            }
+
+            script.push(self.sql_removed(old));
+            script.push(self.sql_added(new));
+
+            return script;
+        }
+
+        if deferrability {
+            let deferrability = if !new.deferrable {
+                "not deferrable"
+            } else if new.deferred {
+                "deferrable initially deferred"
+            } else {
+                "deferrable initially immediate"
+            };
+
+            script.push(format!(
+                "alter {} {} alter constraint \"{}\" {deferrability};\n",
+                new.parent_type, new.parent_name, new.name
+            ));
+        }
+
+        // A validated constraint can’t be marked as not valid again
+        if !old.validated && new.validated {
+            script.push(self.validate(new));
+        }
+
+        script
+    }
+}
+
+diff!(Index, (), crate::inspect::Index);
+
+impl Index {
+    fn sql_added(&self, new: &crate::inspect::Index) -> Script {
+        let mut definition = new.definition.clone();
+
+        if let Some(tablespace) = &new.tablespace {
+            definition = Self::with_tablespace(&definition, tablespace);
+        }
+
+        if self.options().online {
+            let definition = definition.replacen(" INDEX ", " INDEX CONCURRENTLY ", 1);
+
+            Script {
+                after_commit: format!("{definition};\n"),
+                ..Default::default()
+            }
+        } else {
+            format!("{definition};\n").into()
+        }
+    }
+
+    /**
+     * The tablespace goes before the `where` clause of partial indexes.
+     */
+    fn with_tablespace(definition: &str, tablespace: &str) -> String {
+        let tablespace = format!(" TABLESPACE \"{tablespace}\"");
+
+        match definition.find(" WHERE ") {
+            Some(x) => format!("{}{tablespace}{}", &definition[..x], &definition[x..]),
+            None => format!("{definition}{tablespace}"),
+        }
+    }
+
+    fn sql_removed(&self, old: &crate::inspect::Index) -> Script {
+        if self.options().online {
+            Script {
+                after_commit: format!("drop index concurrently {};\n", old.fullname()),
+                ..Default::default()
+            }
+        } else {
+            format!("drop index {};\n", old.fullname()).into()
+        }
+    }
+
+    fn sql_updated(&self, old: &crate::inspect::Index, new: &crate::inspect::Index) -> Script {
+        if old.signature() == new.signature() {
+            let mut sql = String::new();
+
+            if old.name != new.name {
+                sql.push_str(&format!(
+                    "alter index {} rename to \"{}\";\n",
+                    old.fullname(),
+                    new.name
+                ));
+            }
+
+            sql.push_str(&alter_storage(
+                &format!("index {}", new.fullname()),
+                (&old.parameters, old.tablespace.as_deref()),
+                (&new.parameters, new.tablespace.as_deref()),
+            ));
+
+            return sql.into();
+        }
+
+        let mut script = Script::default();
+
+        script.push(self.sql_removed(old));
+        script.push(self.sql_added(new));
+
+        script
+    }
+}
+
+fn storage(parameters: &[String], tablespace: Option<&str>) -> String {
+    let mut sql = String::new();
+
+    if !parameters.is_empty() {
+        sql.push_str(&format!(" with ({})", parameters.join(", ")));
+    }
+
+    if let Some(tablespace) = tablespace {
+        sql.push_str(&format!(" tablespace \"{tablespace}\""));
+    }
+
+    sql
+}
+
+/**
+ * `generated` clause of a column definition.
+ */
+/**
+ * Grants `privileges` on `table`, views included.
+ */
+fn grants(privileges: &[crate::inspect::Privilege], table: &str) -> String {
+    let mut sql = String::new();
+
+    for privilege in privileges {
+        sql.push_str(&format!(
+            "grant {} on table {table} to {}{};\n",
+            privilege.privileges.join(", ").to_lowercase(),
+            privilege.grantee(),
+            if privilege.grantable {
+                " with grant option"
+            } else {
+                ""
+            },
+        ));
+    }
+
+    sql
+}
+
+fn generation(column: &crate::inspect::Column) -> String {
+    if let Some(expression) = &column.generated {
+        return format!(" generated always as ({expression}) stored");
+    }
+
+    let Some(identity) = &column.identity else {
+        return String::new();
+    };
+
+    let cycle = if identity.cycle { "cycle" } else { "no cycle" };
+
+    format!(
+        " generated {} as identity (start with {} increment by {} minvalue {} maxvalue {} cache {} {cycle})",
+        identity.generation,
+        identity.start,
+        identity.increment,
+        identity.min,
+        identity.max,
+        identity.cache,
+    )
+}
+
+/**
+ * Alters storage parameters and tablespace, given as `(parameters,
+ * tablespace)`.
+ */
+fn alter_storage(
+    object: &str,
+    old: (&[String], Option<&str>),
+    new: (&[String], Option<&str>),
+) -> String {
+    let mut sql = String::new();
+
+    let old_parameters = old.0.iter().map(|x| option(x)).collect::<BTreeMap<_, _>>();
+    let new_parameters = new.0.iter().map(|x| option(x)).collect::<BTreeMap<_, _>>();
+
+    let set = new_parameters
+        .iter()
+        .filter(|(key, value)| old_parameters.get(*key) != Some(value))
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>();
+
+    if !set.is_empty() {
+        sql.push_str(&format!("alter {object} set ({});\n", set.join(", ")));
+    }
+
+    let reset = old_parameters
+        .keys()
+        .filter(|key| !new_parameters.contains_key(*key))
+        .copied()
+        .collect::<Vec<_>>();
+
+    if !reset.is_empty() {
+        sql.push_str(&format!("alter {object} reset ({});\n", reset.join(", ")));
+    }
+
+    if old.1 != new.1 {
+        sql.push_str(&format!(
+            "alter {object} set tablespace \"{}\";\n",
+            new.1.unwrap_or("pg_default")
+        ));
+    }
+
+    sql
+}
+
+/**
+ * Sets and resets the `name=value` configuration parameters of `object`.
+ */
+fn alter_settings(object: &str, old: &[String], new: &[String]) -> String {
+    let mut sql = String::new();
+
+    let old = old.iter().map(|x| option(x)).collect::<BTreeMap<_, _>>();
+    let new = new.iter().map(|x| option(x)).collect::<BTreeMap<_, _>>();
+
+    for (key, value) in &new {
+        if old.get(key) != Some(value) {
+            sql.push_str(&format!("alter {object} set {key} = {};\n", literal(value)));
+        }
+    }
+
+    for key in old.keys() {
+        if !new.contains_key(key) {
+            sql.push_str(&format!("alter {object} reset {key};\n"));
+        }
+    }
+
+    sql
+}
+
+/**
+ * Truncates `name` to `NAMEDATALEN - 1` bytes, as PostgreSQL does.
+ */
+fn identifier(name: &str) -> String {
+    let mut len = name.len().min(63);
+
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    name[..len].to_string()
+}
+
+fn literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn option(option: &str) -> (&str, &str) {
+    option.split_once('=').unwrap_or((option, ""))
+}
+
+fn is_secret(key: &str) -> bool {
+    key == "password"
+}
+
+/**
+ * Secrets aren’t copied, a commented-out statement is left to set them by
+ * hand.
+ */
+fn secret(object: &str, action: &str, key: &str) -> String {
+    format!(
+        "-- warning: {key} of {object} isn’t copied, set it with:\n-- alter {object} options ({action} {key} '********');\n"
+    )
+}
+
+/**
+ * `options` clause without the secrets, see `secrets`.
+ */
+fn options(options: &[String]) -> String {
+    let options = options
+        .iter()
+        .map(|x| option(x))
+        .filter(|(key, _)| !is_secret(key))
+        .map(|(key, value)| format!("{key} {}", literal(value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if options.is_empty() {
+        return String::new();
+    }
+
+    format!(" options ({options})")
+}
+
+/**
+ * Placeholders setting the secrets of a new `object`.
+ */
+fn secrets(object: &str, options: &[String]) -> String {
+    options
+        .iter()
+        .map(|x| option(x).0)
+        .filter(|key| is_secret(key))
+        .map(|key| secret(object, "add", key))
+        .collect()
+}
+
+fn alter_options(object: &str, old: &[String], new: &[String]) -> String {
+    let old = old.iter().map(|x| option(x)).collect::<BTreeMap<_, _>>();
+    let new = new.iter().map(|x| option(x)).collect::<BTreeMap<_, _>>();
+    let mut actions = Vec::new();
+    let mut secrets = String::new();
+
+    for (key, value) in &new {
+        let action = match old.get(key) {
+            Some(old) if old == value => continue,
+            Some(_) => "set",
+            None => "add",
+        };
+
+        if is_secret(key) {
+            secrets.push_str(&secret(object, action, key));
+        } else {
+            actions.push(format!("{action} {key} {}", literal(value)));
+        }
+    }
+
+    for key in old.keys() {
+        if !new.contains_key(key) {
+            actions.push(format!("drop {key}"));
+        }
+    }
+
+    if actions.is_empty() {
+        return secrets;
+    }
+
+    format!(
+        "alter {object} options ({});\n{secrets}",
+        actions.join(", ")
+    )
 }
 
 fn comment(ty: &str, fullname: &str, old: Option<&str>, new: Option<&str>) -> String {
@@ -3,18 +3,704 @@ use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Database {
+    pub name: String,
     pub schemas: BTreeMap<String, Schema>,
+    pub event_triggers: BTreeMap<String, EventTrigger>,
+    pub servers: BTreeMap<String, ForeignServer>,
+    pub publications: BTreeMap<String, Publication>,
+    pub subscriptions: BTreeMap<String, Subscription>,
+    pub casts: BTreeMap<String, Cast>,
+    pub extensions: BTreeMap<String, Extension>,
+    pub roles: BTreeMap<String, Role>,
+    pub memberships: BTreeMap<String, Membership>,
+    pub settings: BTreeMap<String, Setting>,
+    pub default_privileges: BTreeMap<String, DefaultPrivilege>,
 }
 
 impl Database {
     pub fn new(dsn: &str) -> crate::Result<Self> {
+        Self::with_options(dsn, &crate::Options::default())
+    }
+
+    /**
+     * Inspects the database, and the cluster roles matching `options.roles`.
+     */
+    pub fn with_options(dsn: &str, options: &crate::Options) -> crate::Result<Self> {
         let conn = elephantry::Connection::new(dsn)?;
+        let name = conn.query_one::<String>("select current_database()::text", &[])?;
+
+        let members = conn
+            .query::<ExtensionMember>(
+                r#"
+select d.classid::regclass::text as "catalog",
+    d.objid as "oid",
+    ext.extname as "extension"
+from pg_catalog.pg_depend d
+    join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+where d.classid in (
+        'pg_catalog.pg_class'::regclass,
+        'pg_catalog.pg_type'::regclass,
+        'pg_catalog.pg_proc'::regclass
+    )
+    and d.deptype = 'e'
+"#,
+                &[],
+            )?
+            .collect::<Vec<_>>();
+
         let schemas = elephantry::inspect::database(&conn)?
             .iter()
-            .map(|x| Ok((x.name.clone(), Schema::new(x, &conn)?)))
+            .map(|x| Ok((x.name.clone(), Schema::new(x, &members, &conn)?)))
             .collect::<crate::Result<BTreeMap<String, Schema>>>()?;
 
-        Ok(Self { schemas })
+        let event_triggers = conn
+            .query::<EventTriggerDefinition>(
+                r#"
+select e.evtname as "name",
+    e.evtevent as "event",
+    e.evttags as "tags",
+    e.evtfoid::regproc::text as "function",
+    e.evtenabled::text as "state"
+from pg_catalog.pg_event_trigger e
+order by e.evtname
+"#,
+                &[],
+            )?
+            .map(|x| (x.name.clone(), EventTrigger::new(x)))
+            .collect();
+
+        let servers = conn
+            .query::<ForeignServerDefinition>(
+                r#"
+select s.oid as "oid",
+    s.srvname as "name",
+    w.fdwname as "wrapper",
+    s.srvtype as "ty",
+    s.srvversion as "version",
+    coalesce(s.srvoptions, '{}') as "options"
+from pg_catalog.pg_foreign_server s
+    join pg_catalog.pg_foreign_data_wrapper w on w.oid = s.srvfdw
+order by s.srvname
+"#,
+                &[],
+            )?
+            .map(|x| Ok((x.name.clone(), ForeignServer::new(x, &conn)?)))
+            .collect::<crate::Result<_>>()?;
+
+        let publications = conn
+            .query::<PublicationDefinition>(
+                r#"
+select p.oid as "oid",
+    p.pubname as "name",
+    p.puballtables as "all_tables",
+    p.pubinsert as "insert",
+    p.pubupdate as "update",
+    p.pubdelete as "delete",
+    p.pubtruncate as "truncate",
+    p.pubviaroot as "via_root"
+from pg_catalog.pg_publication p
+order by p.pubname
+"#,
+                &[],
+            )?
+            .map(|x| Ok((x.name.clone(), Publication::new(x, &conn)?)))
+            .collect::<crate::Result<_>>()?;
+
+        // `subconninfo` is only readable by the superusers
+        let readable = conn.query_one::<bool>(
+            "select pg_catalog.has_column_privilege('pg_catalog.pg_subscription', 'subconninfo', 'select')",
+            &[],
+        )?;
+        let connection = if readable { "s.subconninfo" } else { "null" };
+
+        let subscriptions = conn
+            .query::<Subscription>(
+                &format!(
+                    r#"
+select s.subname as "name",
+    {connection} as "connection",
+    s.subpublications as "publications",
+    s.subenabled as "enabled"
+from pg_catalog.pg_subscription s
+    join pg_catalog.pg_database d on d.oid = s.subdbid
+where d.datname = current_database()
+order by s.subname
+"#
+                ),
+                &[],
+            )?
+            .map(|x| (x.name.clone(), x))
+            .collect();
+
+        let casts = conn
+            .query::<Cast>(
+                r#"
+select pg_catalog.format_type(c.castsource, null) as "source",
+    pg_catalog.format_type(c.casttarget, null) as "target",
+    case c.castmethod when 'f' then c.castfunc::regprocedure::text end as "function",
+    c.castmethod = 'i' as "inout",
+    case c.castcontext when 'a' then 'assignment' when 'i' then 'implicit' end as "context",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_cast'::regclass
+            and d.objid = c.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_cast c
+where c.oid >= 16384
+    -- Range to multirange casts are created with the multirange type
+    and not exists (
+        select 1
+        from pg_catalog.pg_depend d
+        where d.classid = 'pg_catalog.pg_cast'::regclass
+            and d.objid = c.oid
+            and d.deptype = 'i'
+    )
+order by 1, 2
+"#,
+                &[],
+            )?
+            .map(|x| (format!("{} as {}", x.source, x.target), x))
+            .collect();
+
+        let extensions = conn
+            .query::<Extension>(
+                r#"
+select e.extname as "name",
+    n.nspname as "schema",
+    e.extversion as "version"
+from pg_catalog.pg_extension e
+    join pg_catalog.pg_namespace n on n.oid = e.extnamespace
+order by e.extname
+"#,
+                &[],
+            )?
+            .map(|x| (x.name.clone(), x))
+            .collect();
+
+        let (roles, memberships) = match &options.roles {
+            Some(pattern) => (
+                Self::roles(&conn, pattern)?,
+                Self::memberships(&conn, pattern)?,
+            ),
+            None => (BTreeMap::new(), BTreeMap::new()),
+        };
+
+        let settings = conn
+            .query::<Setting>(
+                r#"
+select d.datname::text as "database",
+    case when s.setrole <> 0 then pg_catalog.pg_get_userbyid(s.setrole)::text end as "role",
+    s.setconfig as "settings"
+from pg_catalog.pg_db_role_setting s
+    join pg_catalog.pg_database d on d.oid = s.setdatabase
+where d.datname = current_database()
+order by 2 nulls first
+"#,
+                &[],
+            )?
+            .map(|x| (x.role.clone().unwrap_or_default(), x))
+            .collect();
+
+        let default_privileges = conn
+            .query::<DefaultPrivilege>(
+                r#"
+select pg_catalog.pg_get_userbyid(d.defaclrole)::text as "role",
+    n.nspname as "schema",
+    case d.defaclobjtype
+        when 'r' then 'tables'
+        when 'S' then 'sequences'
+        when 'f' then 'functions'
+        when 'T' then 'types'
+        when 'n' then 'schemas'
+    end as "ty",
+    case when g.grantee <> 0 then pg_catalog.pg_get_userbyid(g.grantee)::text end as "grantee",
+    array(
+        select lower(a.privilege_type)
+        from pg_catalog.aclexplode(d.defaclacl) a
+        where a.grantee = g.grantee
+        order by 1
+    ) as "privileges",
+    array(
+        select lower(a.privilege_type)
+        from pg_catalog.aclexplode(x.defaults) a
+        where a.grantee = g.grantee
+        order by 1
+    ) as "defaults"
+from pg_catalog.pg_default_acl d
+    left join pg_catalog.pg_namespace n on n.oid = d.defaclnamespace
+    cross join lateral (
+        -- Global entries replace the built-in defaults, per schema ones extend them.
+        -- `aclexplode` rejects an empty array, null is used instead
+        select case
+            when d.defaclnamespace = 0 then
+                pg_catalog.acldefault(
+                    case d.defaclobjtype when 'S' then 's' else d.defaclobjtype end,
+                    d.defaclrole
+                )
+        end as defaults
+    ) x
+    cross join lateral (
+        select distinct a.grantee
+        from pg_catalog.aclexplode(d.defaclacl || x.defaults) a
+    ) g
+order by 1, 2 nulls first, 3, 4 nulls first
+"#,
+                &[],
+            )?
+            .map(|x| (x.key(), x))
+            .collect();
+
+        Ok(Self {
+            name,
+            schemas,
+            event_triggers,
+            servers,
+            publications,
+            subscriptions,
+            casts,
+            extensions,
+            roles,
+            memberships,
+            settings,
+            default_privileges,
+        })
+    }
+
+    fn roles(
+        conn: &elephantry::Connection,
+        pattern: &str,
+    ) -> crate::Result<BTreeMap<String, Role>> {
+        // `pg_roles` masks the passwords, they are never read
+        let roles = conn
+            .query::<Role>(
+                r#"
+select r.rolname as "name",
+    r.rolcanlogin as "login",
+    r.rolinherit as "inherit",
+    r.rolcreatedb as "createdb",
+    r.rolcreaterole as "createrole",
+    r.rolconnlimit as "connection_limit",
+    r.rolvaliduntil::text as "valid_until",
+    coalesce(
+        (
+            select s.setconfig
+            from pg_catalog.pg_db_role_setting s
+            where s.setrole = r.oid
+                and s.setdatabase = 0
+        ),
+        '{}'
+    ) as "settings"
+from pg_catalog.pg_roles r
+where r.rolname like $1
+    and r.rolname !~ '^pg_'
+order by r.rolname
+"#,
+                &[&pattern],
+            )?
+            .map(|x| (x.name.clone(), x))
+            .collect();
+
+        Ok(roles)
+    }
+
+    fn memberships(
+        conn: &elephantry::Connection,
+        pattern: &str,
+    ) -> crate::Result<BTreeMap<String, Membership>> {
+        let memberships = conn
+            .query::<Membership>(
+                r#"
+select distinct g.rolname as "role",
+    m.rolname as "member"
+from pg_catalog.pg_auth_members a
+    join pg_catalog.pg_roles g on g.oid = a.roleid
+    join pg_catalog.pg_roles m on m.oid = a.member
+where m.rolname like $1
+    and m.rolname !~ '^pg_'
+order by 1, 2
+"#,
+                &[&pattern],
+            )?
+            .map(|x| (x.fullname(), x))
+            .collect();
+
+        Ok(memberships)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Role {
+    pub name: String,
+    pub login: bool,
+    pub inherit: bool,
+    pub createdb: bool,
+    pub createrole: bool,
+    /**
+     * `-1` for no limit.
+     */
+    pub connection_limit: i32,
+    pub valid_until: Option<String>,
+    /**
+     * `name=value` settings of the role in every database.
+     */
+    pub settings: Vec<String>,
+}
+
+impl Role {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\"", self.name)
+    }
+}
+
+/**
+ * Configuration parameters set for the inspected database, with `alter
+ * database … set` or `alter role … in database … set`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Setting {
+    pub database: String,
+    /**
+     * `None` for the settings of every role.
+     */
+    pub role: Option<String>,
+    /**
+     * `name=value` parameters.
+     */
+    pub settings: Vec<String>,
+}
+
+impl Setting {
+    pub fn fullname(&self) -> String {
+        match &self.role {
+            Some(role) => format!("role \"{role}\" in database \"{}\"", self.database),
+            None => format!("database \"{}\"", self.database),
+        }
+    }
+}
+
+/**
+ * Privileges granted by default to a grantee on the objects created by a
+ * role.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct DefaultPrivilege {
+    pub role: String,
+    /**
+     * `None` for the objects created in every schema.
+     */
+    pub schema: Option<String>,
+    /**
+     * `tables`, `sequences`, `functions`, `types` or `schemas`.
+     */
+    pub ty: String,
+    /**
+     * `None` for `public`.
+     */
+    pub grantee: Option<String>,
+    pub privileges: Vec<String>,
+    /**
+     * Privileges of the grantee without this entry.
+     */
+    pub defaults: Vec<String>,
+}
+
+impl DefaultPrivilege {
+    fn key(&self) -> String {
+        format!(
+            "{}.{}.{}.{}",
+            self.role,
+            self.schema.as_deref().unwrap_or_default(),
+            self.ty,
+            self.grantee.as_deref().unwrap_or("public")
+        )
+    }
+
+    /**
+     * `alter default privileges` clauses selecting the objects.
+     */
+    pub fn target(&self) -> String {
+        let mut target = format!("for role \"{}\"", self.role);
+
+        if let Some(schema) = &self.schema {
+            target.push_str(&format!(" in schema \"{schema}\""));
+        }
+
+        target
+    }
+
+    pub fn grantee(&self) -> String {
+        match &self.grantee {
+            Some(grantee) => format!("\"{grantee}\""),
+            None => "public".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Membership {
+    pub role: String,
+    pub member: String,
+}
+
+impl Membership {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\" to \"{}\"", self.role, self.member)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct PublicationDefinition {
+    oid: elephantry::pq::Oid,
+    name: String,
+    all_tables: bool,
+    insert: bool,
+    update: bool,
+    delete: bool,
+    truncate: bool,
+    via_root: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Publication {
+    pub name: String,
+    pub all_tables: bool,
+    pub insert: bool,
+    pub update: bool,
+    pub delete: bool,
+    pub truncate: bool,
+    /**
+     * Changes of partitions are published as their root table.
+     */
+    pub via_root: bool,
+    pub tables: BTreeMap<String, PublicationTable>,
+}
+
+impl Publication {
+    fn new(
+        publication: PublicationDefinition,
+        conn: &elephantry::Connection,
+    ) -> crate::Result<Self> {
+        let tables = conn
+            .query::<PublicationTable>(
+                r#"
+select format('%I.%I', n.nspname, c.relname) as "name",
+    n.nspname || '.' || c.relname as "relation",
+    (
+        select array_agg(a.attname::text order by a.attnum)
+        from pg_catalog.pg_attribute a
+        where a.attrelid = r.prrelid
+            and a.attnum = any(r.prattrs::int2[])
+    ) as "columns",
+    pg_catalog.pg_get_expr(r.prqual, r.prrelid) as "filter"
+from pg_catalog.pg_publication_rel r
+    join pg_catalog.pg_class c on c.oid = r.prrelid
+    join pg_catalog.pg_namespace n on n.oid = c.relnamespace
+where r.prpubid = $1
+order by 1
+"#,
+                &[&publication.oid],
+            )?
+            .map(|x| (x.name.clone(), x))
+            .collect();
+
+        Ok(Self {
+            name: publication.name,
+            all_tables: publication.all_tables,
+            insert: publication.insert,
+            update: publication.update,
+            delete: publication.delete,
+            truncate: publication.truncate,
+            via_root: publication.via_root,
+            tables,
+        })
+    }
+
+    pub fn fullname(&self) -> String {
+        format!("\"{}\"", self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct PublicationTable {
+    /**
+     * Quoted and qualified table name.
+     */
+    pub name: String,
+    /**
+     * Unquoted `schema.relation` name.
+     */
+    pub relation: String,
+    pub columns: Option<Vec<String>>,
+    /**
+     * Row filter of the `where` clause.
+     */
+    pub filter: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Subscription {
+    pub name: String,
+    /**
+     * Connection string, `None` if it can’t be read.
+     */
+    pub connection: Option<String>,
+    pub publications: Vec<String>,
+    pub enabled: bool,
+}
+
+impl Subscription {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\"", self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Cast {
+    pub source: String,
+    pub target: String,
+    /**
+     * Signature of the cast function, `None` for a binary coercible or an
+     * I/O conversion cast.
+     */
+    pub function: Option<String>,
+    pub inout: bool,
+    /**
+     * `assignment` or `implicit`, `None` for an explicit cast.
+     */
+    pub context: Option<String>,
+    pub extension: Option<String>,
+}
+
+impl Cast {
+    pub fn fullname(&self) -> String {
+        format!("({} as {})", self.source, self.target)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct ForeignServerDefinition {
+    oid: elephantry::pq::Oid,
+    name: String,
+    wrapper: String,
+    ty: Option<String>,
+    version: Option<String>,
+    options: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq)]
+pub struct ForeignServer {
+    pub name: String,
+    pub wrapper: String,
+    pub ty: Option<String>,
+    pub version: Option<String>,
+    /**
+     * Options as `key=value`.
+     */
+    pub options: Vec<String>,
+    pub user_mappings: BTreeMap<String, UserMapping>,
+}
+
+impl ForeignServer {
+    fn new(server: ForeignServerDefinition, conn: &elephantry::Connection) -> crate::Result<Self> {
+        let user_mappings = conn
+            .query::<UserMapping>(
+                r#"
+select u.usename as "user",
+    u.srvname as "server",
+    coalesce(u.umoptions, '{}') as "options"
+from pg_catalog.pg_user_mappings u
+where u.srvid = $1
+order by u.usename
+"#,
+                &[&server.oid],
+            )?
+            .map(|x| (x.user.clone(), x))
+            .collect();
+
+        Ok(Self {
+            name: server.name,
+            wrapper: server.wrapper,
+            ty: server.ty,
+            version: server.version,
+            options: server.options,
+            user_mappings,
+        })
+    }
+
+    pub fn fullname(&self) -> String {
+        format!("\"{}\"", self.name)
+    }
+}
+
+impl PartialEq for ForeignServer {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.wrapper == other.wrapper
+            && self.ty == other.ty
+            && self.version == other.version
+            && self.options == other.options
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct UserMapping {
+    /**
+     * Mapped role, `public` for all of them.
+     */
+    pub user: String,
+    pub server: String,
+    pub options: Vec<String>,
+}
+
+impl UserMapping {
+    pub fn fullname(&self) -> String {
+        let user = if self.user == "public" {
+            "public".to_string()
+        } else {
+            format!("\"{}\"", self.user)
+        };
+
+        format!("for {user} server \"{}\"", self.server)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct EventTriggerDefinition {
+    name: String,
+    event: String,
+    tags: Option<Vec<String>>,
+    function: String,
+    state: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventTrigger {
+    pub name: String,
+    pub event: String,
+    /**
+     * Command tags filtering the event, `None` to fire for all of them.
+     */
+    pub tags: Option<Vec<String>>,
+    pub function: String,
+    pub state: TriggerState,
+}
+
+impl EventTrigger {
+    fn new(event_trigger: EventTriggerDefinition) -> Self {
+        Self {
+            name: event_trigger.name,
+            event: event_trigger.event,
+            tags: event_trigger.tags,
+            function: event_trigger.function,
+            state: TriggerState::new(&event_trigger.state),
+        }
+    }
+
+    pub fn fullname(&self) -> String {
+        format!("\"{}\"", self.name)
     }
 }
 
@@ -26,14 +712,21 @@ pub struct Schema {
     pub enums: BTreeMap<String, Enum>,
     pub domains: BTreeMap<String, Domain>,
     pub composites: BTreeMap<String, Composite>,
-    pub extensions: BTreeMap<String, Extension>,
+    pub ranges: BTreeMap<String, Range>,
     pub functions: BTreeMap<String, Function>,
-    pub triggers: BTreeMap<String, Trigger>,
+    pub collations: BTreeMap<String, Collation>,
+    pub text_search_parsers: BTreeMap<String, TextSearchParser>,
+    pub text_search_dictionaries: BTreeMap<String, TextSearchDictionary>,
+    pub text_search_configurations: BTreeMap<String, TextSearchConfiguration>,
+    pub operators: BTreeMap<String, Operator>,
+    pub operator_families: BTreeMap<String, OperatorFamily>,
+    pub operator_classes: BTreeMap<String, OperatorClass>,
 }
 
 impl Schema {
     fn new(
         inner: &elephantry::inspect::Schema,
+        members: &[ExtensionMember],
         conn: &elephantry::Connection,
     ) -> crate::Result<Self> {
         let mut schema = Self {
@@ -42,74 +735,408 @@ impl Schema {
             enums: BTreeMap::new(),
             domains: BTreeMap::new(),
             composites: BTreeMap::new(),
-            extensions: BTreeMap::new(),
+            ranges: BTreeMap::new(),
             functions: BTreeMap::new(),
-            triggers: BTreeMap::new(),
+            collations: BTreeMap::new(),
+            text_search_parsers: BTreeMap::new(),
+            text_search_dictionaries: BTreeMap::new(),
+            text_search_configurations: BTreeMap::new(),
+            operators: BTreeMap::new(),
+            operator_families: BTreeMap::new(),
+            operator_classes: BTreeMap::new(),
         };
 
+        // Range and multirange constructors are created with their type
+        let constructors = conn
+            .query::<elephantry::pq::Oid>(
+                r#"
+select d.objid
+from pg_catalog.pg_depend d
+where d.classid = 'pg_catalog.pg_proc'::regclass
+    and d.refclassid = 'pg_catalog.pg_type'::regclass
+    and d.deptype = 'i'
+"#,
+                &[],
+            )?
+            .collect::<Vec<_>>();
+
+        // `elephantry::inspect::functions` skips the extension members
+        let member_functions = conn.query::<elephantry::inspect::Function>(
+            r#"
+select p.oid,
+    p.proname as "name",
+    l.lanname as "language",
+    case
+        when l.lanname = 'internal' then p.prosrc
+        else pg_catalog.pg_get_functiondef(p.oid)
+    end as "definition",
+    pg_catalog.pg_get_function_arguments(p.oid) as "arguments",
+    t.typname as "return_type"
+from pg_catalog.pg_proc p
+    join pg_catalog.pg_namespace n on n.oid = p.pronamespace
+    join pg_catalog.pg_language l on l.oid = p.prolang
+    left join pg_catalog.pg_type t on t.oid = p.prorettype
+where n.nspname = $1
+    and p.prokind <> 'a'
+    and exists (
+        select 1
+        from pg_catalog.pg_depend d
+        where d.classid = 'pg_catalog.pg_proc'::regclass
+            and d.objid = p.oid
+            and d.deptype = 'e'
+    )
+order by p.proname
+"#,
+            &[&schema.name],
+        )?;
+
+        // Inspected first: every function keeps a copy of the schema
+        schema.functions = elephantry::inspect::functions(conn, &schema.name)?
+            .into_iter()
+            .chain(member_functions)
+            .filter(|x| !constructors.contains(&x.oid))
+            .map(|x| {
+                let mut function = Function::new(&schema, &x);
+                function.extension = ExtensionMember::find(members, "pg_proc", x.oid);
+
+                (format!("{}.{}", schema.name, x.name), function)
+            })
+            .collect();
+
         schema.relations = elephantry::inspect::schema(conn, &schema.name)?
             .iter()
             .map(|x| {
-                Ok((
-                    format!("{}.{}", schema.name, x.name),
-                    Relation::new(x, conn)?,
-                ))
+                let mut relation = Relation::new(x, conn)?;
+                relation.extension = ExtensionMember::find(members, "pg_class", x.oid);
+
+                Ok((format!("{}.{}", schema.name, x.name), relation))
             })
             .collect::<crate::Result<_>>()?;
 
         schema.enums = elephantry::inspect::enums(conn, &schema.name)?
             .iter()
-            .map(|x| (format!("{}.{}", schema.name, x.name), Enum::new(&schema, x)))
-            .collect();
+            .map(|x| {
+                let mut r#enum = Enum::new(&schema, x);
+                r#enum.extension = ExtensionMember::find_type(members, &x.name, conn)?;
+
+                Ok((format!("{}.{}", schema.name, x.name), r#enum))
+            })
+            .collect::<crate::Result<_>>()?;
 
         schema.domains = elephantry::inspect::domains(conn, &schema.name)?
             .iter()
             .map(|x| {
-                (
-                    format!("{}.{}", schema.name, x.name),
-                    Domain::new(&schema, x),
-                )
+                let mut domain = Domain::new(&schema, x, conn)?;
+                domain.extension = ExtensionMember::find(members, "pg_type", x.oid);
+
+                Ok((format!("{}.{}", schema.name, x.name), domain))
             })
-            .collect();
+            .collect::<crate::Result<_>>()?;
 
         schema.composites = elephantry::inspect::composites(conn, &schema.name)?
             .iter()
             .map(|x| {
-                (
-                    format!("{}.{}", schema.name, x.name),
-                    Composite::new(&schema, x),
-                )
+                let mut composite = Composite::new(&schema, x);
+                composite.extension = ExtensionMember::find_type(members, &x.name, conn)?;
+
+                Ok((format!("{}.{}", schema.name, x.name), composite))
             })
+            .collect::<crate::Result<_>>()?;
+
+        schema.ranges = conn
+            .query::<Range>(
+                r#"
+select n.nspname as "schema",
+    t.typname as "name",
+    pg_catalog.format_type(r.rngsubtype, null) as "subtype",
+    case when not oc.opcdefault then format('%I.%I', ocn.nspname, oc.opcname) end as "subtype_opclass",
+    case
+        when r.rngcollation <> st.typcollation then format('%I.%I', cn.nspname, c.collname)
+        else null
+    end as "collation",
+    nullif(r.rngcanonical::oid, 0)::regproc::text as "canonical",
+    nullif(r.rngsubdiff::oid, 0)::regproc::text as "subtype_diff",
+    (
+        select format('%I.%I', mn.nspname, m.typname)
+        from pg_catalog.pg_type m
+            join pg_catalog.pg_namespace mn on mn.oid = m.typnamespace
+        where m.oid = (to_jsonb(r) ->> 'rngmultitypid')::oid
+    ) as "multirange",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_type'::regclass
+            and d.objid = t.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_range r
+    join pg_catalog.pg_type t on t.oid = r.rngtypid
+    join pg_catalog.pg_namespace n on n.oid = t.typnamespace
+    join pg_catalog.pg_type st on st.oid = r.rngsubtype
+    join pg_catalog.pg_opclass oc on oc.oid = r.rngsubopc
+    join pg_catalog.pg_namespace ocn on ocn.oid = oc.opcnamespace
+    left join pg_catalog.pg_collation c on c.oid = r.rngcollation
+    left join pg_catalog.pg_namespace cn on cn.oid = c.collnamespace
+where n.nspname = $1
+order by t.typname
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}", schema.name, x.name), x))
             .collect();
 
-        schema.extensions = elephantry::inspect::extensions(conn, &schema.name)?
-            .iter()
-            .map(|x| {
-                (
-                    format!("{}.{}", schema.name, x.name),
-                    Extension::new(&schema, x),
-                )
-            })
+        schema.collations = conn
+            .query::<Collation>(
+                r#"
+select n.nspname as "schema",
+    c.collname as "name",
+    case c.collprovider when 'i' then 'icu' when 'b' then 'builtin' else 'libc' end as "provider",
+    coalesce(to_jsonb(c) ->> 'colllocale', to_jsonb(c) ->> 'colliculocale') as "locale",
+    c.collcollate as "lc_collate",
+    c.collctype as "lc_ctype",
+    c.collisdeterministic as "deterministic",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_collation'::regclass
+            and d.objid = c.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_collation c
+    join pg_catalog.pg_namespace n on n.oid = c.collnamespace
+where n.nspname = $1
+order by c.collname
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}", schema.name, x.name), x))
             .collect();
 
-        schema.functions = elephantry::inspect::functions(conn, &schema.name)?
-            .iter()
-            .map(|x| {
-                (
-                    format!("{}.{}", schema.name, x.name),
-                    Function::new(&schema, x),
-                )
-            })
+        schema.text_search_parsers = conn
+            .query::<TextSearchParser>(
+                r#"
+select n.nspname as "schema",
+    p.prsname as "name",
+    p.prsstart::text as "start",
+    p.prstoken::text as "token",
+    p.prsend::text as "end",
+    nullif(p.prsheadline::oid, 0)::regproc::text as "headline",
+    p.prslextype::text as "lextypes",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_ts_parser'::regclass
+            and d.objid = p.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_ts_parser p
+    join pg_catalog.pg_namespace n on n.oid = p.prsnamespace
+where n.nspname = $1
+order by p.prsname
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}", schema.name, x.name), x))
             .collect();
 
-        schema.triggers = elephantry::inspect::triggers(conn, &schema.name)?
-            .iter()
+        schema.text_search_dictionaries = conn
+            .query::<TextSearchDictionary>(
+                r#"
+select n.nspname as "schema",
+    d.dictname as "name",
+    format('%I.%I', tn.nspname, t.tmplname) as "template",
+    d.dictinitoption as "options",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend e
+            join pg_catalog.pg_extension ext on ext.oid = e.refobjid
+        where e.classid = 'pg_catalog.pg_ts_dict'::regclass
+            and e.objid = d.oid
+            and e.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_ts_dict d
+    join pg_catalog.pg_namespace n on n.oid = d.dictnamespace
+    join pg_catalog.pg_ts_template t on t.oid = d.dicttemplate
+    join pg_catalog.pg_namespace tn on tn.oid = t.tmplnamespace
+where n.nspname = $1
+order by d.dictname
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}", schema.name, x.name), x))
+            .collect();
+
+        schema.text_search_configurations = conn
+            .query::<TextSearchConfigurationDefinition>(
+                r#"
+select c.oid,
+    n.nspname as "schema",
+    c.cfgname as "name",
+    format('%I.%I', pn.nspname, p.prsname) as "parser",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_ts_config'::regclass
+            and d.objid = c.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_ts_config c
+    join pg_catalog.pg_namespace n on n.oid = c.cfgnamespace
+    join pg_catalog.pg_ts_parser p on p.oid = c.cfgparser
+    join pg_catalog.pg_namespace pn on pn.oid = p.prsnamespace
+where n.nspname = $1
+order by c.cfgname
+"#,
+                &[&schema.name],
+            )?
             .map(|x| {
-                (
+                Ok((
                     format!("{}.{}", schema.name, x.name),
-                    Trigger::new(&schema, x),
-                )
+                    TextSearchConfiguration::new(x, conn)?,
+                ))
             })
+            .collect::<crate::Result<_>>()?;
+
+        schema.operators = conn
+            .query::<Operator>(
+                r#"
+select n.nspname as "schema",
+    o.oprname as "name",
+    case when o.oprleft <> 0 then pg_catalog.format_type(o.oprleft, null) end as "left",
+    pg_catalog.format_type(o.oprright, null) as "right",
+    format('%I.%I', fn.nspname, f.proname) as "function",
+    (
+        select format('operator(%I.%s)', cn.nspname, c.oprname)
+        from pg_catalog.pg_operator c
+            join pg_catalog.pg_namespace cn on cn.oid = c.oprnamespace
+        where c.oid = o.oprcom
+    ) as "commutator",
+    (
+        select format('operator(%I.%s)', cn.nspname, c.oprname)
+        from pg_catalog.pg_operator c
+            join pg_catalog.pg_namespace cn on cn.oid = c.oprnamespace
+        where c.oid = o.oprnegate
+    ) as "negator",
+    nullif(o.oprrest::oid, 0)::regproc::text as "restrict",
+    nullif(o.oprjoin::oid, 0)::regproc::text as "join",
+    o.oprcanhash as "hashes",
+    o.oprcanmerge as "merges",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_operator'::regclass
+            and d.objid = o.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_operator o
+    join pg_catalog.pg_namespace n on n.oid = o.oprnamespace
+    join pg_catalog.pg_proc f on f.oid = o.oprcode
+    join pg_catalog.pg_namespace fn on fn.oid = f.pronamespace
+where n.nspname = $1
+order by o.oprname, 3, 4
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}", schema.name, x.signature()), x))
+            .collect();
+
+        schema.operator_families = conn
+            .query::<OperatorFamily>(
+                r#"
+select n.nspname as "schema",
+    f.opfname as "name",
+    m.amname as "method",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_opfamily'::regclass
+            and d.objid = f.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_opfamily f
+    join pg_catalog.pg_namespace n on n.oid = f.opfnamespace
+    join pg_catalog.pg_am m on m.oid = f.opfmethod
+where n.nspname = $1
+order by f.opfname, m.amname
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}.{}", schema.name, x.name, x.method), x))
+            .collect();
+
+        schema.operator_classes = conn
+            .query::<OperatorClass>(
+                r#"
+select n.nspname as "schema",
+    c.opcname as "name",
+    m.amname as "method",
+    pg_catalog.format_type(c.opcintype, null) as "ty",
+    format('%I.%I', fn.nspname, f.opfname) as "family",
+    c.opcdefault as "default",
+    case when c.opckeytype <> 0 then pg_catalog.format_type(c.opckeytype, null) end as "storage",
+    array(
+        select format(
+            'operator %s %I.%s (%s, %s)',
+            a.amopstrategy,
+            opn.nspname,
+            o.oprname,
+            pg_catalog.format_type(a.amoplefttype, null),
+            pg_catalog.format_type(a.amoprighttype, null)
+        ) || case
+            when a.amoppurpose = 'o' then format(' for order by %I.%I', sn.nspname, s.opfname)
+            else ''
+        end
+        from pg_catalog.pg_amop a
+            join pg_catalog.pg_depend d on d.classid = 'pg_catalog.pg_amop'::regclass
+                and d.objid = a.oid
+                and d.refclassid = 'pg_catalog.pg_opclass'::regclass
+                and d.refobjid = c.oid
+            join pg_catalog.pg_operator o on o.oid = a.amopopr
+            join pg_catalog.pg_namespace opn on opn.oid = o.oprnamespace
+            left join pg_catalog.pg_opfamily s on s.oid = a.amopsortfamily
+            left join pg_catalog.pg_namespace sn on sn.oid = s.opfnamespace
+        order by a.amopstrategy
+    ) as "operators",
+    array(
+        select format(
+            'function %s (%s, %s) %s',
+            p.amprocnum,
+            pg_catalog.format_type(p.amproclefttype, null),
+            pg_catalog.format_type(p.amprocrighttype, null),
+            p.amproc::regprocedure
+        )
+        from pg_catalog.pg_amproc p
+            join pg_catalog.pg_depend d on d.classid = 'pg_catalog.pg_amproc'::regclass
+                and d.objid = p.oid
+                and d.refclassid = 'pg_catalog.pg_opclass'::regclass
+                and d.refobjid = c.oid
+        order by p.amprocnum
+    ) as "functions",
+    (
+        select ext.extname
+        from pg_catalog.pg_depend d
+            join pg_catalog.pg_extension ext on ext.oid = d.refobjid
+        where d.classid = 'pg_catalog.pg_opclass'::regclass
+            and d.objid = c.oid
+            and d.deptype = 'e'
+    ) as "extension"
+from pg_catalog.pg_opclass c
+    join pg_catalog.pg_namespace n on n.oid = c.opcnamespace
+    join pg_catalog.pg_am m on m.oid = c.opcmethod
+    join pg_catalog.pg_opfamily f on f.oid = c.opcfamily
+    join pg_catalog.pg_namespace fn on fn.oid = f.opfnamespace
+where n.nspname = $1
+order by c.opcname, m.amname
+"#,
+                &[&schema.name],
+            )?
+            .map(|x| (format!("{}.{}.{}", schema.name, x.name, x.method), x))
             .collect();
 
         Ok(schema)
@@ -126,6 +1153,115 @@ impl PartialEq for Schema {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct RelationSize {
+    tuples: i64,
+    pages: i32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct Storage {
+    parameters: Vec<String>,
+    tablespace: Option<String>,
+}
+
+impl Storage {
+    fn new(oid: elephantry::pq::Oid, conn: &elephantry::Connection) -> crate::Result<Self> {
+        let storage = conn.query_one::<Self>(
+            r#"
+select coalesce(c.reloptions, '{}') as "parameters",
+    t.spcname as "tablespace"
+from pg_catalog.pg_class c
+    left join pg_catalog.pg_tablespace t on t.oid = c.reltablespace
+where c.oid = $1
+"#,
+            &[&oid],
+        )?;
+
+        Ok(storage)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct ColumnAttributes {
+    name: String,
+    statistics: i32,
+    collation: Option<String>,
+    storage: String,
+    default_storage: String,
+    compression: Option<String>,
+    generated: Option<String>,
+    identity: Option<String>,
+    identity_start: Option<i64>,
+    identity_increment: Option<i64>,
+    identity_min: Option<i64>,
+    identity_max: Option<i64>,
+    identity_cache: Option<i64>,
+    identity_cycle: Option<bool>,
+    sequence: Option<String>,
+}
+
+impl Default for ColumnAttributes {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            statistics: -1,
+            collation: None,
+            storage: "extended".to_string(),
+            default_storage: "extended".to_string(),
+            compression: None,
+            generated: None,
+            identity: None,
+            identity_start: None,
+            identity_increment: None,
+            identity_min: None,
+            identity_max: None,
+            identity_cache: None,
+            identity_cycle: None,
+            sequence: None,
+        }
+    }
+}
+
+impl ColumnAttributes {
+    fn identity(&self) -> Option<Identity> {
+        let generation = self.identity.clone()?;
+
+        Some(Identity {
+            generation,
+            start: self.identity_start.unwrap_or(1),
+            increment: self.identity_increment.unwrap_or(1),
+            min: self.identity_min.unwrap_or(1),
+            max: self.identity_max.unwrap_or(i64::MAX),
+            cache: self.identity_cache.unwrap_or(1),
+            cycle: self.identity_cycle.unwrap_or_default(),
+        })
+    }
+}
+
+/**
+ * Identity of a column and the options of its sequence.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Identity {
+    /**
+     * `always` or `by default`.
+     */
+    pub generation: String,
+    pub start: i64,
+    pub increment: i64,
+    pub min: i64,
+    pub max: i64,
+    pub cache: i64,
+    pub cycle: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct ForeignTable {
+    server: String,
+    options: Vec<String>,
+}
+
 #[derive(Clone, Debug, Deref, Eq)]
 pub struct Relation {
     #[deref]
@@ -133,6 +1269,49 @@ pub struct Relation {
     pub columns: BTreeMap<String, Column>,
     pub constraints: BTreeMap<String, Constraint>,
     pub indexes: BTreeMap<String, Index>,
+    pub triggers: BTreeMap<String, Trigger>,
+    pub rules: BTreeMap<String, Rule>,
+    pub statistics: BTreeMap<String, Statistic>,
+    /**
+     * Storage parameters, as `key=value`.
+     */
+    pub parameters: Vec<String>,
+    /**
+     * Tablespace, `None` for the database default one.
+     */
+    pub tablespace: Option<String>,
+    /**
+     * Server of a foreign table.
+     */
+    pub server: Option<String>,
+    /**
+     * Options of a foreign table, as `key=value`.
+     */
+    pub options: Vec<String>,
+    /**
+     * Estimated number of rows, `-1` if the relation was never analyzed.
+     */
+    pub tuples: i64,
+    pub pages: i32,
+    /**
+     * Extension owning the object, its members are only diffed with
+     * `Options::extension_members`.
+     */
+    pub extension: Option<String>,
+    /**
+     * Owner of a table, with its privileges and row security, they aren’t
+     * diffed but recreated with a rebuilt table.
+     */
+    pub owner: Option<String>,
+    pub privileges: Vec<Privilege>,
+    pub row_security: bool,
+    pub force_row_security: bool,
+    pub policies: Vec<Policy>,
+    /**
+     * Views and materialized views reading a table, directly or through
+     * another view, in creation order.
+     */
+    pub dependents: Vec<Dependent>,
 }
 
 impl Relation {
@@ -140,65 +1319,476 @@ impl Relation {
         relation: &elephantry::inspect::Relation,
         conn: &elephantry::Connection,
     ) -> crate::Result<Self> {
+        let size = conn.query_one::<RelationSize>(
+            r#"
+select c.reltuples::bigint as "tuples",
+    c.relpages as "pages"
+from pg_catalog.pg_class c
+where c.oid = $1
+"#,
+            &[&relation.oid],
+        )?;
+
+        let storage = Storage::new(relation.oid, conn)?;
+
         let mut relation = Self {
             inner: relation.clone(),
             columns: BTreeMap::new(),
             constraints: BTreeMap::new(),
             indexes: BTreeMap::new(),
+            triggers: BTreeMap::new(),
+            rules: BTreeMap::new(),
+            statistics: BTreeMap::new(),
+            parameters: storage.parameters,
+            tablespace: storage.tablespace,
+            server: None,
+            options: Vec::new(),
+            tuples: size.tuples,
+            pages: size.pages,
+            extension: None,
+            owner: None,
+            privileges: Vec::new(),
+            row_security: false,
+            force_row_security: false,
+            policies: Vec::new(),
+            dependents: Vec::new(),
         };
 
+        if relation.kind == elephantry::inspect::Kind::OrdinaryTable {
+            relation.security(conn)?;
+        }
+
+        if relation.kind == elephantry::inspect::Kind::ForeignTable {
+            let foreign_table = conn.query_one::<ForeignTable>(
+                r#"
+select s.srvname as "server",
+    coalesce(f.ftoptions, '{}') as "options"
+from pg_catalog.pg_foreign_table f
+    join pg_catalog.pg_foreign_server s on s.oid = f.ftserver
+where f.ftrelid = $1
+"#,
+                &[&relation.oid],
+            )?;
+
+            relation.server = Some(foreign_table.server);
+            relation.options = foreign_table.options;
+        }
+
+        let mut attributes = conn
+            .query::<ColumnAttributes>(
+                r#"
+select a.attname as "name",
+    coalesce(a.attstattarget, -1)::int4 as "statistics",
+    case
+        when a.attcollation <> t.typcollation then format('%I.%I', n.nspname, c.collname)
+    end as "collation",
+    case a.attstorage
+        when 'p' then 'plain' when 'e' then 'external' when 'm' then 'main' else 'extended'
+    end as "storage",
+    case t.typstorage
+        when 'p' then 'plain' when 'e' then 'external' when 'm' then 'main' else 'extended'
+    end as "default_storage",
+    case a.attcompression when 'p' then 'pglz' when 'l' then 'lz4' end as "compression",
+    case
+        when a.attgenerated = 's' then pg_catalog.pg_get_expr(ad.adbin, ad.adrelid)
+    end as "generated",
+    case a.attidentity when 'a' then 'always' when 'd' then 'by default' end as "identity",
+    s.seqstart as "identity_start",
+    s.seqincrement as "identity_increment",
+    s.seqmin as "identity_min",
+    s.seqmax as "identity_max",
+    s.seqcache as "identity_cache",
+    s.seqcycle as "identity_cycle",
+    (
+        select format('%I.%I', sn.nspname, sc.relname)
+        from pg_catalog.pg_depend sd
+            join pg_catalog.pg_class sc on sc.oid = sd.objid
+            join pg_catalog.pg_namespace sn on sn.oid = sc.relnamespace
+        where sd.classid = 'pg_catalog.pg_class'::regclass
+            and sd.refobjid = a.attrelid
+            and sd.refobjsubid = a.attnum
+            and sd.deptype = 'a'
+            and sc.relkind = 'S'
+    ) as "sequence"
+from pg_catalog.pg_attribute a
+    join pg_catalog.pg_type t on t.oid = a.atttypid
+    left join pg_catalog.pg_collation c on c.oid = a.attcollation
+    left join pg_catalog.pg_namespace n on n.oid = c.collnamespace
+    left join pg_catalog.pg_attrdef ad on ad.adrelid = a.attrelid and ad.adnum = a.attnum
+    left join pg_catalog.pg_depend d on a.attidentity <> ''
+        and d.classid = 'pg_catalog.pg_class'::regclass
+        and d.refobjid = a.attrelid
+        and d.refobjsubid = a.attnum
+        and d.deptype = 'i'
+    left join pg_catalog.pg_sequence s on s.seqrelid = d.objid
+where a.attrelid = $1
+    and a.attnum > 0
+    and not a.attisdropped
+"#,
+                &[&relation.oid],
+            )?
+            .map(|x| (x.name.clone(), x))
+            .collect::<BTreeMap<_, _>>();
+
         relation.columns = elephantry::inspect::relation(conn, &relation.schema, &relation.name)?
             .iter()
-            .map(|x| {
+            .enumerate()
+            .map(|(position, x)| {
+                let attributes = attributes.remove(&x.name).unwrap_or_default();
+
                 (
                     format!("{}.{}.{}", relation.schema, relation.name, x.name),
-                    Column::new(&relation, x),
+                    Column::new(&relation, x, position + 1, attributes),
                 )
             })
             .collect();
 
         relation.constraints = elephantry::inspect::constraints(conn, relation.oid)?
             .iter()
+            // Constraint triggers are diffed with the other triggers
+            .filter(|x| x.ty != elephantry::inspect::constraint::Type::Trigger)
+            .map(|x| {
+                // A table has a single primary key, it’s replaced whatever its name
+                let name = if x.ty == elephantry::inspect::constraint::Type::PrimaryKey {
+                    "primary key"
+                } else {
+                    &x.name
+                };
+
+                Ok((
+                    format!("{}.{}", relation.fullname(), name),
+                    Constraint::new(&relation.kind.to_string(), &relation.fullname(), x, conn)?,
+                ))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        // Indexes backing a constraint are diffed with it, unlike
+        // `elephantry::inspect::indexes` the ones referenced by a foreign key
+        // are kept
+        relation.indexes = conn
+            .query::<elephantry::inspect::Index>(
+                r#"
+select i.indexrelid as "oid",
+    c.relname as "name",
+    pg_catalog.pg_get_indexdef(i.indexrelid) as "definition"
+from pg_catalog.pg_index i
+    join pg_catalog.pg_class c on c.oid = i.indexrelid
+where i.indrelid = $1
+    and not exists (
+        select 1
+        from pg_catalog.pg_constraint x
+        where x.conindid = i.indexrelid
+            and x.conrelid = i.indrelid
+            and x.contype in ('p', 'u', 'x')
+    )
+order by c.relname
+"#,
+                &[&relation.oid],
+            )?
+            .map(|x| {
+                Ok((
+                    format!("{}.{}", relation.fullname(), x.name),
+                    Index::new(&relation, &x, conn)?,
+                ))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        relation.triggers = conn
+            .query::<TriggerDefinition>(
+                r#"
+select t.tgname as "name",
+    pg_catalog.pg_get_triggerdef(t.oid) as "definition",
+    t.tgenabled::text as "state"
+from pg_catalog.pg_trigger t
+where t.tgrelid = $1
+    and not t.tgisinternal
+order by t.tgname
+"#,
+                &[&relation.oid],
+            )?
             .map(|x| {
                 (
                     format!("{}.{}", relation.fullname(), x.name),
-                    Constraint::new(&relation.kind.to_string(), &relation.fullname(), x),
+                    Trigger::new(&relation, x),
                 )
             })
             .collect();
 
-        relation.indexes = elephantry::inspect::indexes(conn, &relation)?
-            .iter()
+        relation.rules = conn
+            .query::<RuleDefinition>(
+                r#"
+select r.rulename as "name",
+    pg_catalog.pg_get_ruledef(r.oid) as "definition"
+from pg_catalog.pg_rewrite r
+where r.ev_class = $1
+    and r.rulename <> '_RETURN'
+order by r.rulename
+"#,
+                &[&relation.oid],
+            )?
             .map(|x| {
                 (
                     format!("{}.{}", relation.fullname(), x.name),
-                    Index::new(&relation, x),
+                    Rule::new(&relation, x),
                 )
             })
             .collect();
 
+        relation.statistics = conn
+            .query::<Statistic>(
+                r#"
+select n.nspname as "schema",
+    s.stxname as "name",
+    pg_catalog.pg_get_statisticsobjdef(s.oid) as "definition",
+    coalesce(s.stxstattarget, -1)::int4 as "target"
+from pg_catalog.pg_statistic_ext s
+    join pg_catalog.pg_namespace n on n.oid = s.stxnamespace
+where s.stxrelid = $1
+order by s.stxname
+"#,
+                &[&relation.oid],
+            )?
+            .map(|x| (format!("{}.{}", relation.fullname(), x.name), x))
+            .collect();
+
         Ok(relation)
     }
 
+    fn security(&mut self, conn: &elephantry::Connection) -> crate::Result {
+        let security = conn.query_one::<RelationSecurity>(
+            r#"
+select pg_catalog.pg_get_userbyid(c.relowner)::text as "owner",
+    c.relrowsecurity as "row_security",
+    c.relforcerowsecurity as "force_row_security"
+from pg_catalog.pg_class c
+where c.oid = $1
+"#,
+            &[&self.oid],
+        )?;
+
+        self.owner = Some(security.owner);
+        self.row_security = security.row_security;
+        self.force_row_security = security.force_row_security;
+
+        self.privileges = Privilege::list(self.oid, conn)?;
+
+        self.policies = conn
+            .query::<Policy>(
+                r#"
+select p.polname as "name",
+    p.polpermissive as "permissive",
+    case p.polcmd
+        when 'r' then 'select' when 'a' then 'insert' when 'w' then 'update' when 'd' then 'delete'
+        else 'all'
+    end as "command",
+    array(
+        select case when r.oid = 0 then 'public' else format('%I', pg_catalog.pg_get_userbyid(r.oid)) end
+        from unnest(p.polroles) r(oid)
+        order by 1
+    ) as "roles",
+    pg_catalog.pg_get_expr(p.polqual, p.polrelid) as "using",
+    pg_catalog.pg_get_expr(p.polwithcheck, p.polrelid) as "check"
+from pg_catalog.pg_policy p
+where p.polrelid = $1
+order by p.polname
+"#,
+                &[&self.oid],
+            )?
+            .collect();
+
+        self.dependents = conn
+            .query::<DependentDefinition>(
+                r#"
+with recursive dependents(oid, depth) as (
+    select r.ev_class, 1
+    from pg_catalog.pg_depend d
+        join pg_catalog.pg_rewrite r on r.oid = d.objid
+    where d.classid = 'pg_catalog.pg_rewrite'::regclass
+        and d.refclassid = 'pg_catalog.pg_class'::regclass
+        and d.refobjid = $1
+        and r.ev_class <> $1
+    union
+    select r.ev_class, x.depth + 1
+    from dependents x
+        join pg_catalog.pg_depend d on d.refobjid = x.oid
+        join pg_catalog.pg_rewrite r on r.oid = d.objid
+    where d.classid = 'pg_catalog.pg_rewrite'::regclass
+        and d.refclassid = 'pg_catalog.pg_class'::regclass
+        and r.ev_class <> x.oid
+)
+select c.oid,
+    n.nspname as "schema",
+    c.relname as "name",
+    c.relkind as "kind",
+    pg_catalog.pg_get_viewdef(c.oid) as "definition",
+    pg_catalog.obj_description(c.oid, 'pg_class') as "comment",
+    pg_catalog.pg_get_userbyid(c.relowner)::text as "owner",
+    array(
+        select pg_catalog.pg_get_indexdef(i.indexrelid)
+        from pg_catalog.pg_index i
+        where i.indrelid = c.oid
+        order by 1
+    ) as "indexes"
+from dependents x
+    join pg_catalog.pg_class c on c.oid = x.oid
+    join pg_catalog.pg_namespace n on n.oid = c.relnamespace
+group by c.oid, n.nspname, c.relname, c.relkind
+order by max(x.depth), n.nspname, c.relname
+"#,
+                &[&self.oid],
+            )?
+            .map(|x| Dependent::new(x, conn))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(())
+    }
+
     pub fn fullname(&self) -> String {
         format!("\"{}\".\"{}\"", self.schema, self.name)
     }
+
+    /**
+     * Column names in table order.
+     */
+    pub fn column_names(&self) -> Vec<&str> {
+        let mut columns = self.columns.values().collect::<Vec<_>>();
+        columns.sort_by_key(|x| x.position);
+
+        columns.iter().map(|x| x.name.as_str()).collect()
+    }
 }
 
 impl PartialEq for Relation {
     fn eq(&self, other: &Self) -> bool {
         self.inner.kind == other.inner.kind
             && self.inner.name == other.inner.name
+            && self.column_names() == other.column_names()
+            && self.server == other.server
+            && self.options == other.options
+            && self.inner.persistence == other.inner.persistence
+            && self.parameters == other.parameters
+            && self.tablespace == other.tablespace
             && self.inner.comment == other.inner.comment
             && self.inner.definition == other.inner.definition
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct RelationSecurity {
+    owner: String,
+    row_security: bool,
+    force_row_security: bool,
+}
+
+/**
+ * Privileges granted on a table, `grantee` is `None` for `public`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Privilege {
+    pub grantee: Option<String>,
+    pub privileges: Vec<String>,
+    pub grantable: bool,
+}
+
+impl Privilege {
+    /**
+     * Privileges granted on the relation `oid`, except the ones of its owner
+     * which come back with it.
+     */
+    fn list(oid: elephantry::pq::Oid, conn: &elephantry::Connection) -> crate::Result<Vec<Self>> {
+        let privileges = conn
+            .query::<Self>(
+                r#"
+select case when a.grantee <> 0 then pg_catalog.pg_get_userbyid(a.grantee)::text end as "grantee",
+    array_agg(a.privilege_type::text order by a.privilege_type) as "privileges",
+    a.is_grantable as "grantable"
+from pg_catalog.pg_class c
+    cross join pg_catalog.aclexplode(c.relacl) a
+where c.oid = $1
+    and a.grantee <> c.relowner
+group by a.grantee, a.is_grantable
+order by 1, 3
+"#,
+                &[&oid],
+            )?
+            .collect();
+
+        Ok(privileges)
+    }
+
+    pub fn grantee(&self) -> String {
+        match &self.grantee {
+            Some(grantee) => format!("\"{grantee}\""),
+            None => "public".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Policy {
+    pub name: String,
+    pub permissive: bool,
+    pub command: String,
+    /**
+     * Quoted roles, `public` included.
+     */
+    pub roles: Vec<String>,
+    pub using: Option<String>,
+    pub check: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct DependentDefinition {
+    oid: elephantry::pq::Oid,
+    schema: String,
+    name: String,
+    kind: elephantry::inspect::Kind,
+    definition: String,
+    comment: Option<String>,
+    owner: String,
+    indexes: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dependent {
+    pub schema: String,
+    pub name: String,
+    pub kind: elephantry::inspect::Kind,
+    pub definition: String,
+    pub comment: Option<String>,
+    pub owner: String,
+    pub privileges: Vec<Privilege>,
+    /**
+     * Index definitions of a materialized view.
+     */
+    pub indexes: Vec<String>,
+}
+
+impl Dependent {
+    fn new(dependent: DependentDefinition, conn: &elephantry::Connection) -> crate::Result<Self> {
+        Ok(Self {
+            privileges: Privilege::list(dependent.oid, conn)?,
+            schema: dependent.schema,
+            name: dependent.name,
+            kind: dependent.kind,
+            definition: dependent.definition,
+            comment: dependent.comment,
+            owner: dependent.owner,
+            indexes: dependent.indexes,
+        })
+    }
+
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
 #[derive(Clone, Debug, Deref, Eq)]
 pub struct Enum {
     #[deref]
     inner: elephantry::inspect::Enum,
     pub parent: Schema,
+    pub extension: Option<String>,
 }
 
 impl Enum {
@@ -206,6 +1796,7 @@ impl Enum {
         Self {
             parent: schema.clone(),
             inner: r#enum.clone(),
+            extension: None,
         }
     }
 
@@ -220,29 +1811,93 @@ impl PartialEq for Enum {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct DomainDefinition {
+    base_type: String,
+    collation: Option<String>,
+    owner: String,
+}
+
 #[derive(Clone, Debug, Deref, Eq)]
 pub struct Domain {
     #[deref]
     inner: elephantry::inspect::Domain,
     pub parent: Schema,
     pub constraints: BTreeMap<String, Constraint>,
+    pub base_type: String,
+    pub collation: Option<String>,
+    pub owner: String,
+    /**
+     * Table columns of this type.
+     */
+    pub columns: Vec<DomainColumn>,
+    pub extension: Option<String>,
 }
 
 impl Domain {
-    fn new(schema: &Schema, domain: &elephantry::inspect::Domain) -> Self {
+    fn new(
+        schema: &Schema,
+        domain: &elephantry::inspect::Domain,
+        conn: &elephantry::Connection,
+    ) -> crate::Result<Self> {
+        let definition = conn.query_one::<DomainDefinition>(
+            r#"
+select pg_catalog.format_type(t.typbasetype, t.typtypmod) as "base_type",
+    case
+        when t.typcollation <> bt.typcollation then format('%I.%I', n.nspname, c.collname)
+        else null
+    end as "collation",
+    pg_catalog.pg_get_userbyid(t.typowner) as "owner"
+from pg_catalog.pg_type t
+    join pg_catalog.pg_type bt on bt.oid = t.typbasetype
+    left join pg_catalog.pg_collation c on c.oid = t.typcollation
+    left join pg_catalog.pg_namespace n on n.oid = c.collnamespace
+where t.oid = $1
+"#,
+            &[&domain.oid],
+        )?;
+
+        let columns = conn
+            .query::<DomainColumn>(
+                r#"
+select n.nspname as "schema",
+    c.relname as "table",
+    a.attname as "name"
+from pg_catalog.pg_attribute a
+    join pg_catalog.pg_class c on c.oid = a.attrelid
+    join pg_catalog.pg_namespace n on n.oid = c.relnamespace
+where a.atttypid = $1
+    and c.relkind in ('r', 'p')
+    and not a.attisdropped
+order by 1, 2
+"#,
+                &[&domain.oid],
+            )?
+            .collect();
+
         let mut d = Self {
             parent: schema.clone(),
             inner: domain.clone(),
             constraints: BTreeMap::new(),
+            base_type: definition.base_type,
+            collation: definition.collation,
+            owner: definition.owner,
+            columns,
+            extension: None,
         };
 
         d.constraints = domain
             .constraints
             .iter()
-            .map(|x| (x.name.clone(), Constraint::new("domain", &d.fullname(), x)))
-            .collect();
+            .map(|x| {
+                Ok((
+                    x.name.clone(),
+                    Constraint::new("domain", &d.fullname(), x, conn)?,
+                ))
+            })
+            .collect::<crate::Result<_>>()?;
 
-        d
+        Ok(d)
     }
 
     pub fn fullname(&self) -> String {
@@ -250,9 +1905,28 @@ impl Domain {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct DomainColumn {
+    pub schema: String,
+    pub table: String,
+    pub name: String,
+}
+
+impl DomainColumn {
+    pub fn table_fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.table)
+    }
+}
+
 impl PartialEq for Domain {
     fn eq(&self, other: &Self) -> bool {
-        self.inner == other.inner
+        self.inner.name == other.inner.name
+            && self.inner.description == other.inner.description
+            && self.inner.is_notnull == other.inner.is_notnull
+            && self.inner.default == other.inner.default
+            && self.base_type == other.base_type
+            && self.collation == other.collation
+            && self.owner == other.owner
     }
 }
 
@@ -261,6 +1935,7 @@ pub struct Composite {
     #[deref]
     inner: elephantry::inspect::Composite,
     pub parent: Schema,
+    pub extension: Option<String>,
 }
 
 impl Composite {
@@ -268,6 +1943,7 @@ impl Composite {
         Self {
             parent: schema.clone(),
             inner: composite.clone(),
+            extension: None,
         }
     }
 
@@ -282,19 +1958,99 @@ impl PartialEq for Composite {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Range {
+    pub schema: String,
+    pub name: String,
+    pub subtype: String,
+    /**
+     * Qualified name of the subtype operator class, `None` for the default
+     * one.
+     */
+    pub subtype_opclass: Option<String>,
+    /**
+     * Qualified name of the collation, `None` for the subtype one.
+     */
+    pub collation: Option<String>,
+    pub canonical: Option<String>,
+    pub subtype_diff: Option<String>,
+    /**
+     * Qualified name of the multirange type, `None` before PostgreSQL 14.
+     */
+    pub multirange: Option<String>,
+    pub extension: Option<String>,
+}
+
+impl Range {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
 #[derive(Clone, Debug, Deref, Eq)]
 pub struct Column {
     #[deref]
     inner: elephantry::inspect::Column,
     pub parent: Relation,
     pub constraints: BTreeMap<String, Constraint>,
+    /**
+     * Ordinal position among the live columns (ordered by `attnum`), as
+     * returned by `select *`.
+     */
+    pub position: usize,
+    /**
+     * Statistics target, `-1` for `default_statistics_target`.
+     */
+    pub statistics: i32,
+    /**
+     * Collation, `None` for the default one of the type.
+     */
+    pub collation: Option<String>,
+    pub storage: String,
+    /**
+     * Storage of the column type.
+     */
+    pub default_storage: String,
+    /**
+     * Compression method, `None` for `default_toast_compression`.
+     */
+    pub compression: Option<String>,
+    /**
+     * Expression of a stored generated column, it isn’t its default.
+     */
+    pub generated: Option<String>,
+    pub identity: Option<Identity>,
+    /**
+     * Sequence owned by the column, as a `serial` one, qualified and quoted.
+     */
+    pub sequence: Option<String>,
 }
 
 impl Column {
-    fn new(relation: &Relation, column: &elephantry::inspect::Column) -> Self {
+    fn new(
+        relation: &Relation,
+        column: &elephantry::inspect::Column,
+        position: usize,
+        attributes: ColumnAttributes,
+    ) -> Self {
+        let mut inner = column.clone();
+
+        if attributes.generated.is_some() {
+            inner.default = None;
+        }
+
         Self {
             parent: relation.clone(),
-            inner: column.clone(),
+            inner,
+            identity: attributes.identity(),
+            generated: attributes.generated,
+            position,
+            statistics: attributes.statistics,
+            collation: attributes.collation,
+            storage: attributes.storage,
+            default_storage: attributes.default_storage,
+            compression: attributes.compression,
+            sequence: attributes.sequence,
             constraints: BTreeMap::new(),
         }
     }
@@ -302,44 +2058,82 @@ impl Column {
     pub fn fullname(&self) -> String {
         format!("{}.\"{}\"", self.parent.fullname(), self.name)
     }
+
+    /**
+     * Same as `elephantry::inspect::Column::ty`, with the precision and scale
+     * of a `numeric` decoded from its type modifier.
+     */
+    pub fn ty(&self) -> String {
+        let ty = self.inner.ty();
+
+        match self.inner.len {
+            Some(len) if ty.starts_with("numeric(") => {
+                let modifier = len - 4;
+                let array = if ty.ends_with("[]") { "[]" } else { "" };
+
+                format!("numeric({},{}){array}", modifier >> 16, modifier & 0xffff)
+            }
+            _ => ty,
+        }
+    }
 }
 
 impl PartialEq for Column {
     fn eq(&self, other: &Self) -> bool {
-        self.inner.is_primary == other.inner.is_primary
-            && self.inner.name == other.inner.name
+        self.inner.name == other.inner.name
             && self.inner.ty() == other.inner.ty()
             && self.inner.default == other.inner.default
             && self.inner.is_notnull == other.inner.is_notnull
             && self.inner.comment == other.inner.comment
+            && self.statistics == other.statistics
+            && self.collation == other.collation
+            && self.storage == other.storage
+            && self.compression == other.compression
+            && self.generated == other.generated
+            && self.identity == other.identity
     }
 }
 
-#[derive(Clone, Debug, Deref, Eq)]
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
 pub struct Extension {
-    #[deref]
-    inner: elephantry::inspect::Extension,
-    pub parent: Schema,
+    pub name: String,
+    pub schema: String,
+    pub version: String,
 }
 
 impl Extension {
-    fn new(schema: &Schema, extension: &elephantry::inspect::Extension) -> Self {
-        Self {
-            parent: schema.clone(),
-            inner: extension.clone(),
-        }
-    }
-
     pub fn fullname(&self) -> String {
-        format!("\"{}\".\"{}\"", self.parent.name, self.name)
+        format!("\"{}\"", self.name)
     }
 }
 
-impl PartialEq for Extension {
-    fn eq(&self, other: &Self) -> bool {
-        self.inner.name == other.inner.name
-            && self.inner.version == other.inner.version
-            && self.inner.description == other.inner.description
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct ExtensionMember {
+    /**
+     * Catalog of the object, as `pg_class`.
+     */
+    catalog: String,
+    oid: elephantry::pq::Oid,
+    extension: String,
+}
+
+impl ExtensionMember {
+    fn find(members: &[Self], catalog: &str, oid: elephantry::pq::Oid) -> Option<String> {
+        members
+            .iter()
+            .find(|x| x.catalog == catalog && x.oid == oid)
+            .map(|x| x.extension.clone())
+    }
+
+    fn find_type(
+        members: &[Self],
+        name: &str,
+        conn: &elephantry::Connection,
+    ) -> crate::Result<Option<String>> {
+        // Enums and composites are only known by their formatted name
+        let oid = conn.query_one::<elephantry::pq::Oid>("select $1::regtype::oid", &[&name])?;
+
+        Ok(Self::find(members, "pg_type", oid))
     }
 }
 
@@ -348,6 +2142,7 @@ pub struct Function {
     #[deref]
     pub inner: elephantry::inspect::Function,
     pub parent: Schema,
+    pub extension: Option<String>,
 }
 
 impl Function {
@@ -355,6 +2150,7 @@ impl Function {
         Self {
             parent: schema.clone(),
             inner: function.clone(),
+            extension: None,
         }
     }
 
@@ -373,23 +2169,363 @@ impl PartialEq for Function {
     }
 }
 
-#[derive(Clone, Debug, Deref, Eq, PartialEq)]
+/**
+ * Collation created with `create collation`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Collation {
+    pub schema: String,
+    pub name: String,
+    /**
+     * `icu`, `libc` or `builtin`.
+     */
+    pub provider: String,
+    /**
+     * Locale of the `icu` and `builtin` providers.
+     */
+    pub locale: Option<String>,
+    pub lc_collate: Option<String>,
+    pub lc_ctype: Option<String>,
+    pub deterministic: bool,
+    pub extension: Option<String>,
+}
+
+impl Collation {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct TextSearchParser {
+    pub schema: String,
+    pub name: String,
+    pub start: String,
+    pub token: String,
+    pub end: String,
+    pub headline: Option<String>,
+    pub lextypes: String,
+    pub extension: Option<String>,
+}
+
+impl TextSearchParser {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct TextSearchDictionary {
+    pub schema: String,
+    pub name: String,
+    /**
+     * Qualified name of the template.
+     */
+    pub template: String,
+    /**
+     * Options of the template, as `name = 'value'` separated by commas.
+     */
+    pub options: Option<String>,
+    pub extension: Option<String>,
+}
+
+impl TextSearchDictionary {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct TextSearchConfigurationDefinition {
+    oid: elephantry::pq::Oid,
+    schema: String,
+    name: String,
+    parser: String,
+    extension: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct TextSearchMapping {
+    token: String,
+    dictionaries: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TextSearchConfiguration {
+    pub schema: String,
+    pub name: String,
+    /**
+     * Qualified name of the parser.
+     */
+    pub parser: String,
+    /**
+     * Qualified dictionaries, in lookup order, by token type.
+     */
+    pub mappings: BTreeMap<String, Vec<String>>,
+    pub extension: Option<String>,
+}
+
+impl TextSearchConfiguration {
+    fn new(
+        configuration: TextSearchConfigurationDefinition,
+        conn: &elephantry::Connection,
+    ) -> crate::Result<Self> {
+        let mappings = conn
+            .query::<TextSearchMapping>(
+                r#"
+select t.alias as "token",
+    array_agg(format('%I.%I', n.nspname, d.dictname) order by m.mapseqno) as "dictionaries"
+from pg_catalog.pg_ts_config c
+    join pg_catalog.pg_ts_config_map m on m.mapcfg = c.oid
+    join lateral pg_catalog.ts_token_type(c.cfgparser) t on t.tokid = m.maptokentype
+    join pg_catalog.pg_ts_dict d on d.oid = m.mapdict
+    join pg_catalog.pg_namespace n on n.oid = d.dictnamespace
+where c.oid = $1
+group by t.alias
+order by t.alias
+"#,
+                &[&configuration.oid],
+            )?
+            .map(|x| (x.token, x.dictionaries))
+            .collect();
+
+        Ok(Self {
+            schema: configuration.schema,
+            name: configuration.name,
+            parser: configuration.parser,
+            mappings,
+            extension: configuration.extension,
+        })
+    }
+
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Operator {
+    pub schema: String,
+    pub name: String,
+    /**
+     * Left operand type, `None` for a prefix operator.
+     */
+    pub left: Option<String>,
+    pub right: String,
+    pub function: String,
+    pub commutator: Option<String>,
+    pub negator: Option<String>,
+    pub restrict: Option<String>,
+    pub join: Option<String>,
+    pub hashes: bool,
+    pub merges: bool,
+    pub extension: Option<String>,
+}
+
+impl Operator {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".{}", self.schema, self.name)
+    }
+
+    /**
+     * Name and operand types, as expected by `drop operator`.
+     */
+    pub fn signature(&self) -> String {
+        format!(
+            "{} ({}, {})",
+            self.name,
+            self.left.as_deref().unwrap_or("none"),
+            self.right
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct OperatorFamily {
+    pub schema: String,
+    pub name: String,
+    /**
+     * Index access method.
+     */
+    pub method: String,
+    pub extension: Option<String>,
+}
+
+impl OperatorFamily {
+    pub fn fullname(&self) -> String {
+        format!(
+            "\"{}\".\"{}\" using {}",
+            self.schema, self.name, self.method
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct OperatorClass {
+    pub schema: String,
+    pub name: String,
+    pub method: String,
+    pub ty: String,
+    /**
+     * Qualified name of the operator family.
+     */
+    pub family: String,
+    pub default: bool,
+    /**
+     * Type stored in the index, `None` for the indexed type.
+     */
+    pub storage: Option<String>,
+    /**
+     * `operator` items of the class, in strategy order.
+     */
+    pub operators: Vec<String>,
+    /**
+     * `function` items of the class, in support number order.
+     */
+    pub functions: Vec<String>,
+    pub extension: Option<String>,
+}
+
+impl OperatorClass {
+    pub fn fullname(&self) -> String {
+        format!(
+            "\"{}\".\"{}\" using {}",
+            self.schema, self.name, self.method
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct TriggerDefinition {
+    name: String,
+    definition: String,
+    state: String,
+}
+
+/**
+ * When a trigger or an event trigger fires, depending on
+ * `session_replication_role`.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TriggerState {
+    Origin,
+    Disabled,
+    Replica,
+    Always,
+}
+
+impl TriggerState {
+    fn new(tgenabled: &str) -> Self {
+        match tgenabled {
+            "D" => Self::Disabled,
+            "R" => Self::Replica,
+            "A" => Self::Always,
+            _ => Self::Origin,
+        }
+    }
+}
+
+impl std::fmt::Display for TriggerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Origin => "enable",
+            Self::Disabled => "disable",
+            Self::Replica => "enable replica",
+            Self::Always => "enable always",
+        };
+
+        f.write_str(s)
+    }
+}
+
+#[derive(Clone, Debug, Eq)]
 pub struct Trigger {
-    #[deref]
-    inner: elephantry::inspect::Trigger,
-    pub parent: Schema,
+    pub name: String,
+    /**
+     * Full `create trigger` statement, from `pg_get_triggerdef`.
+     */
+    pub definition: String,
+    pub state: TriggerState,
+    pub parent: Relation,
 }
 
 impl Trigger {
-    fn new(schema: &Schema, trigger: &elephantry::inspect::Trigger) -> Self {
+    fn new(relation: &Relation, trigger: TriggerDefinition) -> Self {
         Self {
-            parent: schema.clone(),
-            inner: trigger.clone(),
+            name: trigger.name,
+            definition: trigger.definition,
+            state: TriggerState::new(&trigger.state),
+            parent: relation.clone(),
         }
     }
 
     pub fn fullname(&self) -> String {
-        format!("\"{}\".\"{}\"", self.parent.name, self.name)
+        format!("\"{}\" on {}", self.name, self.parent.fullname())
+    }
+}
+
+impl PartialEq for Trigger {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.definition == other.definition && self.state == other.state
+    }
+}
+
+/**
+ * Extended statistics object, from `create statistics`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+pub struct Statistic {
+    pub schema: String,
+    pub name: String,
+    /**
+     * Full `create statistics` statement, from `pg_get_statisticsobjdef`.
+     */
+    pub definition: String,
+    /**
+     * Statistics target, `-1` for the default one.
+     */
+    pub target: i32,
+}
+
+impl Statistic {
+    pub fn fullname(&self) -> String {
+        format!("\"{}\".\"{}\"", self.schema, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct RuleDefinition {
+    name: String,
+    definition: String,
+}
+
+#[derive(Clone, Debug, Eq)]
+pub struct Rule {
+    pub name: String,
+    /**
+     * Full `create rule` statement, from `pg_get_ruledef`.
+     */
+    pub definition: String,
+    pub parent: Relation,
+}
+
+impl Rule {
+    fn new(relation: &Relation, rule: RuleDefinition) -> Self {
+        Self {
+            name: rule.name,
+            definition: rule.definition,
+            parent: relation.clone(),
+        }
+    }
+
+    pub fn fullname(&self) -> String {
+        format!("\"{}\" on {}", self.name, self.parent.fullname())
+    }
+}
+
+impl PartialEq for Rule {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.definition == other.definition
     }
 }
 
@@ -399,6 +2535,23 @@ pub struct Constraint {
     inner: elephantry::inspect::Constraint,
     pub parent_name: String,
     pub parent_type: String,
+    /**
+     * Definition without the validation, deferrability and inheritance
+     * flags.
+     */
+    pub body: String,
+    pub validated: bool,
+    pub deferrable: bool,
+    pub deferred: bool,
+    pub no_inherit: bool,
+    /**
+     * Table referenced by a foreign key, as `schema.relation`.
+     */
+    pub referenced: Option<String>,
+    /**
+     * Existing unique index a primary key is attached to, set by the diff.
+     */
+    pub index: Option<String>,
 }
 
 impl Constraint {
@@ -406,12 +2559,34 @@ impl Constraint {
         parent_type: &str,
         parent_name: &str,
         constraint: &elephantry::inspect::Constraint,
-    ) -> Self {
-        Self {
+        conn: &elephantry::Connection,
+    ) -> crate::Result<Self> {
+        let flags = ConstraintFlags::new(constraint.oid, conn)?;
+
+        // `pg_get_constraintdef` appends the flags in this reverse order
+        let mut body = constraint.definition.as_str();
+
+        for flag in [
+            " NOT VALID",
+            " INITIALLY DEFERRED",
+            " DEFERRABLE",
+            " NO INHERIT",
+        ] {
+            body = body.strip_suffix(flag).unwrap_or(body);
+        }
+
+        Ok(Self {
             parent_name: parent_name.to_string(),
             parent_type: parent_type.to_string(),
+            body: body.to_string(),
+            validated: flags.validated,
+            deferrable: flags.deferrable,
+            deferred: flags.deferred,
+            no_inherit: flags.no_inherit,
+            referenced: flags.referenced,
+            index: None,
             inner: constraint.clone(),
-        }
+        })
     }
 
     pub fn fullname(&self) -> String {
@@ -421,7 +2596,43 @@ impl Constraint {
 
 impl PartialEq for Constraint {
     fn eq(&self, other: &Self) -> bool {
-        self.inner.name == other.inner.name && self.inner.definition == other.inner.definition
+        self.inner.name == other.inner.name
+            && self.inner.ty == other.inner.ty
+            && self.body == other.body
+            && self.validated == other.validated
+            && self.deferrable == other.deferrable
+            && self.deferred == other.deferred
+            && self.no_inherit == other.no_inherit
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, elephantry::Entity)]
+struct ConstraintFlags {
+    validated: bool,
+    deferrable: bool,
+    deferred: bool,
+    no_inherit: bool,
+    referenced: Option<String>,
+}
+
+impl ConstraintFlags {
+    fn new(oid: elephantry::pq::Oid, conn: &elephantry::Connection) -> crate::Result<Self> {
+        let flags = conn.query_one::<Self>(
+            r#"
+select c.convalidated as "validated",
+    c.condeferrable as "deferrable",
+    c.condeferred as "deferred",
+    c.connoinherit as "no_inherit",
+    n.nspname || '.' || r.relname as "referenced"
+from pg_catalog.pg_constraint c
+    left join pg_catalog.pg_class r on r.oid = c.confrelid
+    left join pg_catalog.pg_namespace n on n.oid = r.relnamespace
+where c.oid = $1
+"#,
+            &[&oid],
+        )?;
+
+        Ok(flags)
     }
 }
 
@@ -430,23 +2641,99 @@ pub struct Index {
     #[deref]
     inner: elephantry::inspect::Index,
     pub parent: Relation,
+    /**
+     * Storage parameters, as `key=value`.
+     */
+    pub parameters: Vec<String>,
+    /**
+     * Tablespace, `None` for the database default one.
+     */
+    pub tablespace: Option<String>,
+    /**
+     * `false` for an index whose concurrent build failed.
+     */
+    pub valid: bool,
 }
 
 impl Index {
-    fn new(relation: &Relation, index: &elephantry::inspect::Index) -> Self {
-        Self {
+    fn new(
+        relation: &Relation,
+        index: &elephantry::inspect::Index,
+        conn: &elephantry::Connection,
+    ) -> crate::Result<Self> {
+        let storage = Storage::new(index.oid, conn)?;
+        let valid = conn.query_one::<bool>(
+            "select i.indisvalid from pg_catalog.pg_index i where i.indexrelid = $1",
+            &[&index.oid],
+        )?;
+
+        Ok(Self {
             parent: relation.clone(),
             inner: index.clone(),
-        }
+            parameters: storage.parameters,
+            tablespace: storage.tablespace,
+            valid,
+        })
     }
 
     pub fn fullname(&self) -> String {
-        self.name.clone()
+        format!("\"{}\".\"{}\"", self.parent.schema, self.name)
+    }
+
+    /**
+     * Structure without the index name, an index matching it is renamed
+     * instead of rebuilt.
+     */
+    pub fn signature(&self) -> String {
+        let structure = self.structure();
+
+        match (structure.find(" INDEX "), structure.find(" ON ")) {
+            (Some(start), Some(end)) if start < end => {
+                format!(
+                    "{}{}",
+                    &structure[..start + " INDEX".len()],
+                    &structure[end..]
+                )
+            }
+            _ => structure,
+        }
+    }
+
+    /**
+     * Definition without its storage parameters, changing it requires to
+     * recreate the index.
+     */
+    pub fn structure(&self) -> String {
+        let definition = &self.inner.definition;
+
+        let Some(start) = definition.find(" WITH (") else {
+            return definition.clone();
+        };
+
+        let mut depth = 0;
+
+        for (x, c) in definition[start..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 1 => {
+                    let end = start + x + 1;
+                    return format!("{}{}", &definition[..start], &definition[end..]);
+                }
+                ')' => depth -= 1,
+                _ => (),
+            }
+        }
+
+        definition.clone()
     }
 }
 
 impl PartialEq for Index {
     fn eq(&self, other: &Self) -> bool {
-        self.inner.name == other.inner.name && self.inner.definition == other.inner.definition
+        // The name is kept for the renamed indexes, matched by their signature
+        self.inner.name == other.inner.name
+            && self.signature() == other.signature()
+            && self.parameters == other.parameters
+            && self.tablespace == other.tablespace
     }
 }
@@ -1,5 +1,9 @@
+pub mod conversion;
 pub mod diff;
 pub mod errors;
 pub mod inspect;
+pub mod lock;
+pub mod options;
 
 pub use errors::*;
+pub use options::Options;
@@ -0,0 +1,565 @@
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    ShareUpdateExclusive,
+    Share,
+    ShareRowExclusive,
+    AccessExclusive,
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Level::ShareUpdateExclusive => "SHARE UPDATE EXCLUSIVE",
+            Level::Share => "SHARE",
+            Level::ShareRowExclusive => "SHARE ROW EXCLUSIVE",
+            Level::AccessExclusive => "ACCESS EXCLUSIVE",
+        };
+
+        f.write_str(s)
+    }
+}
+
+/**
+ * Estimated size of a relation, from `pg_class.reltuples` and
+ * `pg_class.relpages`.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Size {
+    pub relation: String,
+    pub tuples: i64,
+    pub pages: i32,
+}
+
+impl std::fmt::Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.tuples < 0 {
+            write!(
+                f,
+                "{} (never analyzed, {} pages)",
+                self.relation, self.pages
+            )
+        } else {
+            write!(
+                f,
+                "{} (~{} rows, {} pages)",
+                self.relation, self.tuples, self.pages
+            )
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Impact {
+    pub lock: Option<Level>,
+    /**
+     * The statement isn't recognized, `lock` is the worst case.
+     */
+    pub unknown: bool,
+    pub rewrite: bool,
+    pub scan: bool,
+    /**
+     * Name of the locked relation, or of the index for `drop index`.
+     */
+    pub relation: Option<String>,
+}
+
+impl Impact {
+    fn new(lock: Level, relation: Option<String>) -> Self {
+        Self {
+            lock: Some(lock),
+            relation,
+            ..Default::default()
+        }
+    }
+
+    fn unknown() -> Self {
+        Self {
+            lock: Some(Level::AccessExclusive),
+            unknown: true,
+            ..Default::default()
+        }
+    }
+
+    fn rewrite(self) -> Self {
+        Self {
+            rewrite: true,
+            ..self
+        }
+    }
+
+    fn scan(self) -> Self {
+        Self { scan: true, ..self }
+    }
+}
+
+/**
+ * Statements only creating new objects, changing cluster wide settings or
+ * objects other than relations, they lock no existing relation.
+ */
+const UNLOCKED: &[&str] = &[
+    "create schema ",
+    "create table ",
+    "create unlogged table ",
+    "create sequence ",
+    "create type ",
+    "create domain ",
+    "create view ",
+    "create materialized view ",
+    "create function ",
+    "create or replace function ",
+    "create procedure ",
+    "create or replace procedure ",
+    "create collation ",
+    "create text search ",
+    "create operator ",
+    "create cast ",
+    "create event trigger ",
+    "comment on schema ",
+    "comment on type ",
+    "comment on domain ",
+    "comment on function ",
+    "alter default privileges ",
+    "alter database ",
+    "alter role ",
+    "create role ",
+    "drop role ",
+    "grant ",
+    "revoke ",
+    "drop schema ",
+    "create extension ",
+    "alter extension ",
+    "drop extension ",
+    "create server ",
+    "alter server ",
+    "drop server ",
+    "create user mapping ",
+    "alter user mapping ",
+    "drop user mapping ",
+    "create foreign table ",
+    "create subscription ",
+    "alter subscription ",
+    "drop subscription ",
+    "drop publication ",
+    "alter type ",
+    "drop type ",
+    "drop domain ",
+    "delete from pg_enum ",
+    "drop function ",
+    "drop procedure ",
+    "alter collation ",
+    "drop collation ",
+    "alter text search ",
+    "drop text search ",
+    "alter operator ",
+    "drop operator ",
+    "drop cast ",
+    "alter event trigger ",
+    "drop event trigger ",
+];
+
+/**
+ * Estimates the lock taken by a statement generated by `Diff::sql`, the
+ * statements it doesn't know are assumed to take an `ACCESS EXCLUSIVE` lock.
+ * `types` maps the lowercased `schema.relation.column` names to their current
+ * type.
+ */
+pub fn analyze(statement: &str, types: &BTreeMap<String, String>) -> Impact {
+    let statement = statement.trim().to_ascii_lowercase();
+    let statement = statement.as_str();
+
+    if let Some(rest) = statement.strip_prefix("alter table ") {
+        let (relation, action) = identifier(rest);
+
+        return alter_table(relation, action.trim_start(), types);
+    }
+
+    if let Some(rest) = statement.strip_prefix("alter index ") {
+        let (index, action) = identifier(rest);
+        let action = action.trim_start();
+
+        return if action.starts_with("set tablespace ") {
+            Impact::new(Level::AccessExclusive, Some(index)).rewrite()
+        } else {
+            Impact::new(Level::ShareUpdateExclusive, Some(index))
+        };
+    }
+
+    if let Some(rest) = statement.strip_prefix("create ") {
+        let rest = rest.strip_prefix("unique ").unwrap_or(rest);
+
+        if let Some(rest) = rest.strip_prefix("index ") {
+            let relation = after(rest, " on ").map(|x| identifier(x.trim_start_matches("only ")).0);
+
+            return if rest.starts_with("concurrently ") {
+                Impact::new(Level::ShareUpdateExclusive, relation).scan()
+            } else {
+                Impact::new(Level::Share, relation).scan()
+            };
+        }
+
+        if rest.starts_with("trigger ")
+            || rest.starts_with("or replace trigger ")
+            || rest.starts_with("constraint trigger ")
+        {
+            let relation = after(rest, " on ").map(|x| identifier(x).0);
+
+            return Impact::new(Level::ShareRowExclusive, relation);
+        }
+
+        if let Some(rest) = rest.strip_prefix("statistics ") {
+            let relation = after(rest, " from ").map(|x| identifier(x).0);
+
+            return Impact::new(Level::ShareUpdateExclusive, relation);
+        }
+
+        if let Some(rest) = rest.strip_prefix("or replace rule ") {
+            let relation = after(rest, " to ").map(|x| identifier(x).0);
+
+            return Impact::new(Level::AccessExclusive, relation);
+        }
+    }
+
+    if let Some(rest) = statement.strip_prefix("drop index ") {
+        return match rest.strip_prefix("concurrently ") {
+            Some(rest) => Impact::new(Level::ShareUpdateExclusive, Some(identifier(rest).0)),
+            None => Impact::new(Level::AccessExclusive, Some(identifier(rest).0)),
+        };
+    }
+
+    if let Some(rest) = statement
+        .strip_prefix("drop trigger ")
+        .or_else(|| statement.strip_prefix("drop rule "))
+    {
+        let relation = after(rest, " on ").map(|x| identifier(x).0);
+
+        return Impact::new(Level::AccessExclusive, relation);
+    }
+
+    if statement.starts_with("alter statistics ") || statement.starts_with("drop statistics ") {
+        return Impact::new(Level::ShareUpdateExclusive, None);
+    }
+
+    for ty in ["table ", "view ", "materialized view ", "foreign table "] {
+        if let Some(rest) = statement.strip_prefix(&format!("drop {ty}")) {
+            let rest = rest.strip_prefix("if exists ").unwrap_or(rest);
+
+            return Impact::new(Level::AccessExclusive, Some(identifier(rest).0));
+        }
+    }
+
+    if let Some(rest) = statement.strip_prefix("alter foreign table ") {
+        return Impact::new(Level::AccessExclusive, Some(identifier(rest).0));
+    }
+
+    // The foreign tables of the server are dropped too
+    if statement.starts_with("drop server ") && statement.ends_with(" cascade;") {
+        return Impact::new(Level::AccessExclusive, None);
+    }
+
+    if let Some(rest) = statement.strip_prefix("alter domain ") {
+        let action = identifier(rest).1.trim_start();
+
+        // The columns of the domain are checked
+        let scan = action.starts_with("validate constraint ")
+            || action.starts_with("set not null")
+            || (action.starts_with("add constraint ") && !action.ends_with(" not valid;"));
+
+        return if scan {
+            Impact::new(Level::Share, None).scan()
+        } else {
+            Impact::default()
+        };
+    }
+
+    if let Some(rest) = statement
+        .strip_prefix("create publication ")
+        .or_else(|| statement.strip_prefix("alter publication "))
+    {
+        return match after(rest, " table ") {
+            Some(tables) => Impact::new(Level::ShareUpdateExclusive, Some(relations(tables))),
+            None => Impact::default(),
+        };
+    }
+
+    if let Some(rest) = statement.strip_prefix("comment on table ") {
+        return Impact::new(Level::ShareUpdateExclusive, Some(identifier(rest).0));
+    }
+
+    if let Some(rest) = statement.strip_prefix("comment on column ") {
+        let column = identifier(rest).0;
+        let relation = column
+            .rsplit_once('.')
+            .map(|(relation, _)| relation.to_string());
+
+        return Impact::new(Level::ShareUpdateExclusive, relation);
+    }
+
+    if UNLOCKED.iter().any(|x| statement.starts_with(x)) {
+        return Impact::default();
+    }
+
+    Impact::unknown()
+}
+
+fn alter_table(relation: String, action: &str, types: &BTreeMap<String, String>) -> Impact {
+    let relation = Some(relation);
+
+    if action.starts_with("validate constraint ") {
+        return Impact::new(Level::ShareUpdateExclusive, relation).scan();
+    }
+
+    if let Some(constraint) = action.strip_prefix("add constraint ") {
+        let foreign = constraint.contains(" foreign key ");
+        let lock = if foreign {
+            Level::ShareRowExclusive
+        } else {
+            Level::AccessExclusive
+        };
+        let impact = Impact::new(lock, relation);
+
+        // Constraints added as not valid or using a built index skip the scan
+        return if constraint.ends_with("not valid;") || constraint.contains(" using index ") {
+            impact
+        } else {
+            impact.scan()
+        };
+    }
+
+    let triggers = [
+        "enable trigger ",
+        "enable replica trigger ",
+        "enable always trigger ",
+        "disable trigger ",
+    ];
+
+    if triggers.iter().any(|x| action.starts_with(x)) || action.starts_with("alter constraint ") {
+        return Impact::new(Level::ShareRowExclusive, relation);
+    }
+
+    if action.starts_with("set (") || action.starts_with("reset (") {
+        return Impact::new(Level::ShareUpdateExclusive, relation);
+    }
+
+    let impact = Impact::new(Level::AccessExclusive, relation);
+
+    if action.starts_with("set logged")
+        || action.starts_with("set unlogged")
+        || action.starts_with("set tablespace ")
+    {
+        return impact.rewrite();
+    }
+
+    // Stored expressions and identities are computed for every existing row
+    if action.starts_with("add column ") && action.contains(" generated ") {
+        return impact.rewrite();
+    }
+
+    if let Some(rest) = action.strip_prefix("alter column ") {
+        if let Some(ty) = after(rest, " type ") {
+            let (column, _) = identifier(rest);
+            let ty = ty.trim_end_matches(';');
+            let new = ty.split(" collate ").next().unwrap_or(ty);
+
+            // A `using` expression is evaluated for every row
+            let coercible = !ty.contains(" using ")
+                && types
+                    .get(&format!(
+                        "{}.{column}",
+                        impact.relation.as_deref().unwrap_or_default()
+                    ))
+                    .is_some_and(|old| {
+                        crate::conversion::classify(old, new)
+                            == crate::conversion::Conversion::BinaryCoercible
+                    });
+
+            return if coercible { impact } else { impact.rewrite() };
+        }
+
+        if action.contains(" set not null") {
+            return impact.scan();
+        }
+
+        if action.contains(" set statistics ") {
+            return Impact::new(Level::ShareUpdateExclusive, impact.relation);
+        }
+    }
+
+    impact
+}
+
+fn after<'a>(s: &'a str, pattern: &str) -> Option<&'a str> {
+    s.find(pattern).map(|x| &s[x + pattern.len()..])
+}
+
+/**
+ * Names of a comma separated list of relations, with their column lists and
+ * row filters.
+ */
+fn relations(s: &str) -> String {
+    let mut relations = Vec::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut start = 0;
+
+    for (x, c) in s.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                relations.push(identifier(s[start..x].trim_start()).0);
+                start = x + 1;
+            }
+            _ => (),
+        }
+    }
+
+    relations.push(identifier(s[start..].trim_start()).0);
+
+    relations.join(", ")
+}
+
+/**
+ * Reads a possibly quoted and qualified identifier, returns it unquoted and
+ * the rest of the string.
+ */
+fn identifier(s: &str) -> (String, &str) {
+    let mut name = String::new();
+    let mut quoted = false;
+    let mut end = s.len();
+
+    for (x, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if !quoted && (c.is_whitespace() || c == ';' || c == '(') => {
+                end = x;
+                break;
+            }
+            c => name.push(c),
+        }
+    }
+
+    (name, &s[end..])
+}
+
+/**
+ * Prefixes every statement of `sql` with its lock impact. `sizes` is keyed by
+ * lowercased relation (or index) names, `types` as in `analyze`.
+ */
+pub(crate) fn annotate(
+    sql: &str,
+    sizes: &BTreeMap<String, Size>,
+    types: &BTreeMap<String, String>,
+) -> String {
+    let mut annotated = String::new();
+
+    for statement in statements(sql) {
+        if !statement.starts_with("--") && !statement.trim().is_empty() {
+            annotated.push_str(&annotation(&analyze(statement, types), sizes));
+        }
+
+        annotated.push_str(statement);
+    }
+
+    annotated
+}
+
+fn annotation(impact: &Impact, sizes: &BTreeMap<String, Size>) -> String {
+    let Some(lock) = impact.lock else {
+        return "-- lock: none\n".to_string();
+    };
+
+    if impact.unknown {
+        return format!("-- lock: unknown (assume {lock})\n");
+    }
+
+    let mut annotation = format!("-- lock: {lock}");
+
+    if let Some(relation) = &impact.relation {
+        match sizes.get(relation) {
+            Some(size) => annotation.push_str(&format!(" on {size}")),
+            None => annotation.push_str(&format!(" on {relation}")),
+        }
+    }
+
+    if impact.rewrite {
+        annotation.push_str(", rewrites the table");
+    } else if impact.scan {
+        annotation.push_str(", scans the table");
+    }
+
+    annotation.push('\n');
+
+    annotation
+}
+
+/**
+ * Splits `sql` into statements and comment lines, keeping the line breaks.
+ */
+fn statements(sql: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+
+    while start < sql.len() {
+        let rest = &sql[start..];
+
+        let len = if rest.starts_with("--") || rest.starts_with('\n') {
+            rest.find('\n').map_or(rest.len(), |x| x + 1)
+        } else {
+            statement_len(rest)
+        };
+
+        statements.push(&rest[..len]);
+        start += len;
+    }
+
+    statements
+}
+
+fn statement_len(sql: &str) -> usize {
+    let mut quote: Option<char> = None;
+    let mut dollar: Option<&str> = None;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((x, c)) = chars.next() {
+        if let Some(tag) = dollar {
+            if sql[x..].starts_with(tag) {
+                for _ in 1..tag.len() {
+                    chars.next();
+                }
+                dollar = None;
+            }
+            continue;
+        }
+
+        match c {
+            '\'' | '"' if quote == Some(c) => quote = None,
+            '\'' | '"' if quote.is_none() => quote = Some(c),
+            '$' if quote.is_none() => {
+                if let Some(end) = sql[x + 1..].find('$') {
+                    let tag = &sql[x..x + end + 2];
+
+                    if tag[1..tag.len() - 1]
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_')
+                    {
+                        for _ in 1..tag.len() {
+                            chars.next();
+                        }
+                        dollar = Some(tag);
+                    }
+                }
+            }
+            ';' if quote.is_none() => {
+                if let Some((_, '\n')) = chars.peek() {
+                    return x + 2;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    sql.len()
+}
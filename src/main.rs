@@ -4,6 +4,24 @@ use clap::Parser;
 struct Args {
     old: String,
     new: String,
+    /// Generates a migration avoiding long blocking locks
+    #[arg(long)]
+    online: bool,
+    /// Annotates every statement with its expected lock level
+    #[arg(long)]
+    report: bool,
+    /// Rebuilds tables to match the target column order
+    #[arg(long)]
+    rebuild: bool,
+    /// Diffs the cluster roles matching this `like` pattern
+    #[arg(long, value_name = "PATTERN")]
+    roles: Option<String>,
+    /// Also diffs the objects created by extensions
+    #[arg(long)]
+    extension_members: bool,
+    /// Loads options from a toml file
+    #[arg(long)]
+    config: Option<std::path::PathBuf>,
 }
 
 fn main() -> pgdiff::Result {
@@ -13,10 +31,23 @@ fn main() -> pgdiff::Result {
 }
 
 fn diff(args: &Args) -> pgdiff::Result {
-    let old = pgdiff::inspect::Database::new(&args.old)?;
-    let new = pgdiff::inspect::Database::new(&args.new)?;
+    let mut options = match &args.config {
+        Some(config) => pgdiff::Options::from_file(config)?,
+        None => pgdiff::Options::default(),
+    };
+    options.online |= args.online;
+    options.report |= args.report;
+    options.rebuild |= args.rebuild;
+    options.extension_members |= args.extension_members;
 
-    let diff = pgdiff::diff::Diff::from(&old, &new);
+    if args.roles.is_some() {
+        options.roles.clone_from(&args.roles);
+    }
+
+    let old = pgdiff::inspect::Database::with_options(&args.old, &options)?;
+    let new = pgdiff::inspect::Database::with_options(&args.new, &options)?;
+
+    let diff = pgdiff::diff::Diff::new(&old, &new, &options);
 
     print!("{}", diff.sql());
 
@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /**
+     * Avoids long blocking locks: indexes are built concurrently and
+     * constraints are validated after the migration transaction.
+     */
+    pub online: bool,
+    /**
+     * Annotates every statement with its expected lock level.
+     */
+    pub report: bool,
+    /**
+     * Rebuilds tables whose column order differs instead of only reporting
+     * it.
+     */
+    pub rebuild: bool,
+    /**
+     * `USING` expressions for column type changes, keyed by
+     * `schema.relation.column`.
+     */
+    pub using: BTreeMap<String, String>,
+    /**
+     * Also diffs the cluster roles whose name matches this `like` pattern.
+     * Roles aren’t inspected when unset.
+     */
+    pub roles: Option<String>,
+    /**
+     * Also diffs the objects created by extensions, they are skipped by
+     * default since the extension manages them.
+     */
+    pub extension_members: bool,
+}
+
+impl Options {
+    /**
+     * Loads options from a toml file.
+     */
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let options = toml::from_str(&contents)?;
+
+        Ok(options)
+    }
+}
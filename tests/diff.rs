@@ -2,11 +2,86 @@ type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 #[test]
 fn diff() -> Result {
-    let actual = load_diff()?;
+    let options = pgdiff::Options::from_file("tests/pgdiff.toml")?;
+
+    let actual = load_diff(&options)?;
     let expected = include_str!("diff.sql");
 
+    assert_same(expected, &actual);
+
+    Ok(())
+}
+
+#[test]
+fn online() -> Result {
+    let options = pgdiff::Options {
+        online: true,
+        ..Default::default()
+    };
+
+    let actual = load_diff(&options)?;
+    let expected = include_str!("online.sql");
+
+    assert_same(expected, &actual);
+
+    Ok(())
+}
+
+#[test]
+fn report() -> Result {
+    let options = pgdiff::Options {
+        report: true,
+        ..Default::default()
+    };
+
+    let actual = load_diff(&options)?;
+    let expected = include_str!("report.sql");
+
+    assert_same(expected, &actual);
+
+    Ok(())
+}
+
+#[test]
+fn rebuild() -> Result {
+    let options = pgdiff::Options {
+        rebuild: true,
+        ..Default::default()
+    };
+
+    let actual = load_diff(&options)?;
+    let expected = include_str!("rebuild.sql");
+
+    assert_same(expected, &actual);
+
+    Ok(())
+}
+
+#[test]
+fn extension_members() -> Result {
+    let options = pgdiff::Options {
+        extension_members: true,
+        ..Default::default()
+    };
+
+    let actual = load_diff(&options)?;
+    let skipped = load_diff(&pgdiff::Options::default())?;
+
+    // A cast and a function of the extensions
+    for member in [
+        "drop cast (text as citext);",
+        "FUNCTION public.xpath_string(text, text)",
+    ] {
+        assert!(actual.contains(member), "{member}");
+        assert!(!skipped.contains(member), "{member}");
+    }
+
+    Ok(())
+}
+
+fn assert_same(expected: &str, actual: &str) {
     if actual != expected {
-        let diff = similar::TextDiff::from_lines(expected, &actual);
+        let diff = similar::TextDiff::from_lines(expected, actual);
 
         for op in diff.ops() {
             for change in diff.iter_changes(op) {
@@ -20,53 +95,135 @@ fn diff() -> Result {
         }
         panic!();
     }
+}
+
+#[test]
+fn syntax() -> Result {
+    let url = std::env::var("NEW_URL").unwrap();
+    let db = elephantry::Connection::new(&url)?;
+
+    let online = pgdiff::Options {
+        online: true,
+        ..Default::default()
+    };
+    let rebuild = pgdiff::Options {
+        rebuild: true,
+        ..Default::default()
+    };
+
+    for options in [pgdiff::Options::default(), online, rebuild] {
+        let diff = load_diff(&options)?;
+        let diff = diff.trim_start_matches("begin;\n\n");
+        // The statements run after the migration commit are checked too
+        let (sql, after_commit) = diff.split_once("commit;\n").unwrap_or((diff, ""));
+
+        for sql in [sql, after_commit] {
+            db.execute(&format!(
+                "do $syntax_check$ begin return;{sql}end; $syntax_check$;"
+            ))?;
+        }
+    }
 
     Ok(())
 }
 
+/**
+ * The roles are shared by both databases, the old ones are restored before
+ * applying the diff.
+ */
 #[test]
-fn syntax() -> Result {
-    let diff = load_diff()?;
-    let diff = diff
-        .trim_start_matches("begin;\n\n")
-        .trim_end_matches("commit;\n");
+fn roles() -> Result {
+    let options = pgdiff::Options {
+        roles: Some("pgdiff\\_%".to_string()),
+        ..Default::default()
+    };
 
-    let sql = format!("do $syntax_check$ begin return;{diff}end; $syntax_check$;");
+    let config = config();
+    let path = "tests/fixtures/roles";
+    let old_sql = std::fs::read_to_string(format!("{path}/old.sql"))?;
 
-    let url = std::env::var("NEW_URL").unwrap();
-    let db = elephantry::Connection::new(&url)?;
-    db.execute(&sql)?;
+    let old_url = create_db(&config.old_url, "roles")?;
+    let old = db(&old_url, &old_sql, &options)?;
+    let new_url = create_db(&config.new_url, "roles")?;
+    let new = db(
+        &new_url,
+        &std::fs::read_to_string(format!("{path}/new.sql"))?,
+        &options,
+    )?;
+
+    let actual = pgdiff::diff::Diff::new(&old, &new, &options).sql();
+    let expected = std::fs::read_to_string(format!("{path}/diff.sql"))?;
+
+    assert_same(&expected, &actual);
+    assert!(!actual.to_lowercase().contains("password"));
+    assert!(!actual.contains("SCRAM"));
+
+    let migrated = db(&old_url, &format!("{old_sql}{actual}"), &options)?;
+
+    assert_eq!(migrated.roles, new.roles);
+    assert_eq!(migrated.memberships, new.memberships);
 
     Ok(())
 }
 
+/**
+ * Creates an empty database named after the one of `url` suffixed by `name`,
+ * returns its url.
+ */
+fn create_db(url: &str, name: &str) -> Result<String> {
+    let (server, database) = url.rsplit_once('/').unwrap();
+    let database = format!("{database}_{name}");
+
+    let db = elephantry::Connection::new(url)?;
+    db.execute(&format!("drop database if exists \"{database}\""))?;
+    db.execute(&format!("create database \"{database}\""))?;
+
+    Ok(format!("{server}/{database}"))
+}
+
 #[derive(envir::Deserialize)]
 struct Config {
     old_url: String,
     new_url: String,
 }
 
-fn load_diff() -> Result<String> {
-    use envir::Deserialize;
+fn config() -> &'static Config {
+    static CONFIG: std::sync::OnceLock<Config> = std::sync::OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        use envir::Deserialize;
+
+        envir::init();
+        Config::from_env().unwrap()
+    })
+}
+
+static DATABASES: std::sync::OnceLock<(pgdiff::inspect::Database, pgdiff::inspect::Database)> =
+    std::sync::OnceLock::new();
+
+fn load_diff(options: &pgdiff::Options) -> Result<String> {
+    let (old, new) = DATABASES.get_or_init(|| {
+        let config = config();
+        let options = pgdiff::Options::default();
+        let old = db(&config.old_url, include_str!("old.sql"), &options).unwrap();
+        let new = db(&config.new_url, include_str!("new.sql"), &options).unwrap();
 
-    envir::init();
-    let config = Config::from_env()?;
-    let old = db(&config.old_url, include_str!("old.sql"))?;
-    let new = db(&config.new_url, include_str!("new.sql"))?;
+        (old, new)
+    });
 
-    let pgdiff = pgdiff::diff::Diff::from(&old, &new);
+    let pgdiff = pgdiff::diff::Diff::new(old, new, options);
 
     let diff = pgdiff.sql();
 
     Ok(diff)
 }
 
-fn db(url: &str, sql: &str) -> Result<pgdiff::inspect::Database> {
+fn db(url: &str, sql: &str, options: &pgdiff::Options) -> Result<pgdiff::inspect::Database> {
     let db = elephantry::Connection::new(url)?;
 
     db.execute(sql)?;
 
-    let diff = pgdiff::inspect::Database::new(url)?;
+    let diff = pgdiff::inspect::Database::with_options(url, options)?;
 
     Ok(diff)
 }
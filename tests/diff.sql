@@ -1,15 +1,165 @@
 begin;
 
+--
+-- Cast
+--
+drop cast (text as bigint);
+
+--
+-- Setting
+--
+alter role "postgres" in database "old" set work_mem = '32MB';
+alter database "old" set work_mem = '16MB';
+alter database "old" reset maintenance_work_mem;
+
+--
+-- Constraint
+--
+alter table "public"."old_referencing" drop constraint "old_referencing_ref_fkey";
+alter table "public"."updated_table" drop constraint "updated_table_old_foreign_fkey";
+
 --
 -- Schema
 --
 create schema new_schema;
 comment on schema new_schema is 'new schema';
+
+--
+-- DefaultPrivilege
+--
+alter default privileges for role "postgres" in schema "new_schema" grant select on tables to public;
+alter default privileges for role "postgres" in schema "public" grant select on tables to public;
+alter default privileges for role "postgres" grant execute on functions to public;
+
+--
+-- Extension
+--
+create extension "xml2" schema "public" version '1.1';
+alter extension "citext" set schema "new_schema";
+alter extension "hstore" update to '1.8';
+
+--
+-- ForeignServer
+--
+create server "new_server" type 'postgres' foreign data wrapper "postgres_fdw" options (host 'remote');
+create user mapping for "postgres" server "new_server";
+-- warning: password of user mapping for "postgres" server "new_server" isn’t copied, set it with:
+-- alter user mapping for "postgres" server "new_server" options (add password '********');
+drop server "old_server" cascade;
+drop server "recreated_server" cascade;
+create server "recreated_server" type 'postgres' foreign data wrapper "postgres_fdw" options (host 'localhost');
+create user mapping for public server "recreated_server" options (user 'remote');
+-- warning: password of user mapping for public server "recreated_server" isn’t copied, set it with:
+-- alter user mapping for public server "recreated_server" options (add password '********');
+alter server "updated_server" version '2';
+alter server "updated_server" options (add dbname 'new', set host 'remote', drop port);
+--
+-- UserMapping
+--
+-- warning: password of user mapping for public server "updated_server" isn’t copied, set it with:
+-- alter user mapping for public server "updated_server" options (set password '********');
+
+
+--
+-- Schema
+--
 drop schema old_schema;
 comment on schema public is 'public schema';
+--
+-- Collation
+--
+create collation "public"."new_collation" (provider = icu, locale = 'und-u-ks-level2', deterministic = false);
+drop collation "public"."updated_collation";
+create collation "public"."updated_collation" (provider = icu, locale = 'de-DE');
+
+--
+-- TextSearchParser
+--
+create text search parser "public"."new_parser" (start = prsd_start, gettoken = prsd_nexttoken, end = prsd_end, lextypes = prsd_lextype, headline = prsd_headline);
+
+--
+-- TextSearchDictionary
+--
+create text search dictionary "public"."new_dictionary" (template = pg_catalog.snowball, language = 'english');
+alter text search dictionary "public"."updated_dictionary" (stopwords = 'french', accept);
+
+--
+-- TextSearchConfiguration
+--
+create text search configuration "public"."new_configuration" (parser = public.new_parser);
+alter text search configuration "public"."new_configuration" add mapping for asciiword, word with public.new_dictionary;
+drop text search configuration "public"."old_configuration";
+alter text search configuration "public"."updated_configuration" drop mapping for url;
+alter text search configuration "public"."updated_configuration" add mapping for email with pg_catalog.simple;
+alter text search configuration "public"."updated_configuration" alter mapping for word with public.new_dictionary;
+
+--
+-- Function
+--
+CREATE OR REPLACE FUNCTION public.new_function()
+ RETURNS trigger
+ LANGUAGE plpgsql
+AS $function$
+begin
+    return new;
+end;
+$function$;
+drop function "public"."updated_function";
+CREATE OR REPLACE FUNCTION public.updated_function()
+ RETURNS trigger
+ LANGUAGE plpgsql
+AS $function$
+begin
+    return old;
+end;
+$function$;
+
+--
+-- Operator
+--
+create operator "public".=== (function = pg_catalog.int4eq, leftarg = integer, rightarg = integer, commutator = operator(public.===), restrict = eqsel, join = eqjoinsel);
+alter operator "public".~~~ (integer, integer) set (restrict = eqsel, join = eqjoinsel);
+
+--
+-- OperatorFamily
+--
+create operator family "public"."new_family" using btree;
+
+--
+-- OperatorClass
+--
+create operator class "public"."new_class" for type integer using btree family public.new_family as
+    operator 3 public.=== (integer, integer),
+    function 1 (integer, integer) btint4cmp(integer,integer);
+
+--
+-- Range
+--
+create type "public"."new_range" as range (subtype = double precision, subtype_diff = float8mi, multirange_type_name = public.new_multirange);
+drop type "public"."updated_range";
+create type "public"."updated_range" as range (subtype = numeric, multirange_type_name = public.updated_multirange);
+
 --
 -- Relation
 --
+create table "public"."measure_table"(
+    sensor int4,
+    taken date
+);
+CREATE STATISTICS public.measure_statistic (dependencies) ON sensor, taken FROM measure_table;
+alter statistics "public"."measure_statistic" set statistics 500;
+create table "public"."new_author"(
+    id int4 primary key,
+    favorite_book int4
+);
+create table "public"."new_book"(
+    id int4 primary key,
+    author int4
+);
+create foreign table "public"."new_foreign_table"(
+    "id" int4,
+    "name" text
+) server "new_server" options (table_name 'new_table');
 create materialized view "public"."new_materialized_view" as  SELECT 1 AS "?column?";
 create view "public"."new_recursive_view" as  WITH RECURSIVE new_recursive_view(pk) AS (
          SELECT 1 AS "?column?"
@@ -20,17 +170,128 @@ create table "public"."new_table"(
     pk int4 primary key
 );
 comment on table "public"."new_table" is 'new table';
+CREATE TRIGGER new_table_trigger BEFORE INSERT ON public.new_table FOR EACH ROW EXECUTE FUNCTION trigger_function();
 create unlogged table "public"."new_unlogged_table"(
 );
 create view "public"."new_view" as  SELECT pk
    FROM new_table;
+CREATE TRIGGER new_view_trigger INSTEAD OF INSERT ON public.new_view FOR EACH ROW EXECUTE FUNCTION trigger_function();
+create foreign table "public"."recreated_foreign_table"(
+    "id" int4
+) server "recreated_server" options (table_name 'remote_table');
+create view "public"."reordered_new_view" as  SELECT r.id,
+    r.number
+   FROM reordered_table r;
+CREATE TRIGGER reordered_new_view_trigger INSTEAD OF UPDATE ON public.reordered_new_view FOR EACH ROW EXECUTE FUNCTION trigger_function();
+drop foreign table if exists "public"."old_foreign_table";
 drop materialized view "public"."old_materialized_view";
+drop table "public"."old_referenced";
+drop table "public"."old_referencing";
 drop table "public"."old_table";
 drop view "public"."old_view";
+-- warning: column order of "public"."identity_table" differs, expected "serial_id", "always_id", "default_id", "dropped_id", "price", "total", "kept", "became", "added_id", "added_total"
+-- warning: column order of "public"."reordered_table" differs, expected "id", "created", "name", "number"
+alter table "public"."storage_table" set unlogged;
+alter table "public"."storage_table" set (autovacuum_vacuum_scale_factor=0.1, fillfactor=80);
+alter table "public"."storage_table" reset (autovacuum_enabled);
+alter foreign table "public"."updated_foreign_table" options (add schema_name 'remote', set table_name 'other_table');
 comment on table "public"."updated_table" is null;
 drop view "public"."updated_view";
 create view "public"."updated_view" as  SELECT pk
    FROM new_table;
+--
+-- Constraint
+--
+alter table "public"."constrained_table" validate constraint "constrained_table_id_check";
+alter table "public"."constrained_table" drop constraint "constrained_table_id_key";
+alter table "public"."constrained_table" add constraint "constrained_table_id_key" UNIQUE (id) DEFERRABLE;
+
+--
+-- Rule
+--
+drop rule "old_rule" on "public"."ft";
+CREATE OR REPLACE RULE updated_rule AS
+    ON UPDATE TO public.ft DO
+ NOTIFY ft;
+
+--
+-- Column
+--
+alter table "public"."identity_table" add column "added_id" int8 generated always as identity (start with 1 increment by 1 minvalue 1 maxvalue 9223372036854775807 cache 1 no cycle);
+alter table "public"."identity_table" add column "added_total" int4 generated always as ((price * 4)) stored;
+alter table "public"."identity_table" alter column "always_id" set generated by default set start with 100 set increment by 10;
+-- warning: recreating generated column "public"."identity_table"."became"
+alter table "public"."identity_table" drop column "became";
+alter table "public"."identity_table" add column "became" int4 generated always as ((price - 1)) stored;
+alter table "public"."identity_table" alter column "default_id" set cycle;
+alter table "public"."identity_table" alter column "dropped_id" drop identity;
+alter table "public"."identity_table" alter column "kept" drop expression;
+alter table "public"."identity_table" alter column "serial_id" drop default;
+alter table "public"."identity_table" alter column "serial_id" add generated by default as identity (start with 1 increment by 1 minvalue 1 maxvalue 2147483647 cache 1 no cycle);
+-- warning: recreating generated column "public"."identity_table"."total"
+alter table "public"."identity_table" drop column "total";
+alter table "public"."identity_table" add column "total" int4 generated always as ((price * 3)) stored;
+
+--
+-- Constraint
+--
+alter table "public"."keyed_table" drop constraint "keyed_table_pkey";
+alter table "public"."keyed_table" add constraint "keyed_table_pkey" PRIMARY KEY (id);
+
+--
+-- Constraint
+--
+alter table "public"."moved_key" drop constraint "moved_key_pkey", add constraint "moved_key_pkey" primary key using index "moved_key_id";
+
+--
+-- Constraint
+--
+alter table "public"."new_key" add constraint "new_key_pkey" primary key using index "new_key_id";
+
+--
+-- Column
+--
+-- warning: converting "public"."price_table"."narrowed" from numeric(10,2) to numeric(8,2) may lose data
+alter table "public"."price_table" alter column "narrowed" type numeric(8,2) using "narrowed"::numeric(8,2);
+-- warning: converting "public"."price_table"."rounded" from numeric(10,2) to numeric(10,1) may lose data
+alter table "public"."price_table" alter column "rounded" type numeric(10,1) using "rounded"::numeric(10,1);
+alter table "public"."price_table" alter column "unconstrained" type numeric;
+alter table "public"."price_table" alter column "widened" type numeric(12,2);
+
+--
+-- Constraint
+--
+alter table "public"."reading_table" add constraint "reading_table_value_check" CHECK ((value > 0));
+
+--
+-- Column
+--
+comment on column "public"."reordered_table"."name" is 'reordered column';
+
+--
+-- Column
+--
+alter table "public"."storage_table" add column "data" text;
+alter table "public"."storage_table" alter column "data" set compression lz4;
+alter table "public"."storage_table" alter column "label" type text collate pg_catalog."POSIX";
+alter table "public"."storage_table" alter column "note" set compression default;
+alter table "public"."storage_table" alter column "payload" set storage external;
+
+--
+-- Index
+--
+alter index "public"."storage_index" set (deduplicate_items=off, fillfactor=90);
+
+--
+-- Trigger
+--
+CREATE TRIGGER triggered_view_trigger INSTEAD OF INSERT ON public.triggered_view FOR EACH ROW EXECUTE FUNCTION trigger_function();
+
+--
+-- Constraint
+--
+alter table "public"."unkeyed_table" add constraint "unkeyed_table_pkey" PRIMARY KEY (id);
+
 --
 -- Column
 --
@@ -39,23 +300,29 @@ alter table "public"."updated_table" add column "new_column" text;
 comment on column "public"."updated_table"."new_column" is 'new column';
 alter table "public"."updated_table" add column "new_foreign" int4;
 alter table "public"."updated_table" drop column "old_column";
+alter table "public"."updated_table" alter column "bigger_column" type int8;
+alter table "public"."updated_table" alter column "json_column" type jsonb using to_jsonb(json_column);
+-- warning: converting "public"."updated_table"."limited_column" from text to varchar(10) may lose data
+alter table "public"."updated_table" alter column "limited_column" type varchar(10) using "limited_column"::varchar(10);
 alter table "public"."updated_table" alter column "new_default" set default now();
 alter table "public"."updated_table" alter column "new_not_null" set not null;
 alter table "public"."updated_table" alter column "old_default" drop default;
+alter table "public"."updated_table" alter column "old_index" set statistics 200;
 alter table "public"."updated_table" alter column "old_not_null" drop not null;
+-- warning: converting "public"."updated_table"."shrunk_column" from varchar(100) to varchar(10) may lose data
+alter table "public"."updated_table" alter column "shrunk_column" type varchar(10) using "shrunk_column"::varchar(10);
 comment on column "public"."updated_table"."updated_column" is 'updated column';
 alter table "public"."updated_table" alter column "updated_column" type int4 using "updated_column"::int4;
+alter table "public"."updated_table" alter column "widened_column" type varchar(20);
 
 --
 -- Constraint
 --
 alter table "public"."updated_table" add constraint "updated_table_new_check_check" CHECK ((char_length(new_check) = 5));
 alter table "public"."updated_table" add constraint "updated_table_new_exclude_excl" EXCLUDE USING gist (new_exclude WITH &&);
-alter table "public"."updated_table" add constraint "updated_table_new_foreign_fkey" FOREIGN KEY (new_foreign) REFERENCES ft(id);
 alter table "public"."updated_table" add constraint "updated_table_new_unique_key" UNIQUE (new_unique);
 alter table "public"."updated_table" drop constraint "updated_table_old_check_check";
 alter table "public"."updated_table" drop constraint "updated_table_old_exclude_excl";
-alter table "public"."updated_table" drop constraint "updated_table_old_foreign_fkey";
 alter table "public"."updated_table" drop constraint "updated_table_old_unique_key";
 alter table "public"."updated_table" drop constraint "updated_table_updated_check_check";
 alter table "public"."updated_table" add constraint "updated_table_updated_check_check" CHECK ((char_length(updated_check) = 2));
@@ -64,10 +331,36 @@ alter table "public"."updated_table" add constraint "updated_table_updated_check
 -- Index
 --
 CREATE INDEX new_index ON public.updated_table USING btree (new_column) WHERE (new_column IS NULL);
-drop index old_index;
-drop index updated_index;
+drop index "public"."old_index";
+alter index "public"."renamed_old_index" rename to "renamed_new_index";
+drop index "public"."updated_index";
 CREATE INDEX updated_index ON public.updated_table USING btree (updated_column) WHERE (updated_column > 10);
 
+--
+-- Trigger
+--
+CREATE CONSTRAINT TRIGGER new_constraint_trigger AFTER INSERT ON public.updated_table DEFERRABLE INITIALLY DEFERRED FOR EACH ROW EXECUTE FUNCTION new_function();
+CREATE TRIGGER new_trigger AFTER UPDATE OF new_column ON public.updated_table FOR EACH ROW WHEN ((old.* IS DISTINCT FROM new.*)) EXECUTE FUNCTION new_function();
+drop trigger "old_trigger" on "public"."updated_table";
+alter table "public"."updated_table" disable trigger "state_trigger";
+drop trigger "updated_trigger" on "public"."updated_table";
+CREATE TRIGGER updated_trigger BEFORE INSERT ON public.updated_table FOR EACH ROW EXECUTE FUNCTION new_function();
+
+--
+-- Statistic
+--
+CREATE STATISTICS public.new_statistic (mcv) ON old_index, new_check FROM updated_table;
+drop statistics "public"."old_statistic";
+alter statistics "public"."target_statistic" set statistics 500;
+drop statistics "public"."updated_statistic";
+CREATE STATISTICS public.updated_statistic (ndistinct) ON old_index, old_check FROM updated_table;
+
+--
+-- Rule
+--
+CREATE OR REPLACE RULE new_rule AS
+    ON INSERT TO public.updated_view DO INSTEAD NOTHING;
+
 
 --
 -- Enum
@@ -80,13 +373,23 @@ alter type "public"."updated_enum" add value 'neutral' after 'sad';
 --
 -- Domain
 --
-create domain "public"."new_domain" as text CHECK ((VALUE ~ '^http://'::text));
+create domain "public"."new_domain" as text constraint "new_domain_check" CHECK ((VALUE ~ '^http://'::text));
 drop domain "public"."old_domain";
+drop domain "public"."retyped_domain";
+create domain "public"."retyped_domain" as text;
 alter domain "public"."updated_domain" set not null;
 alter domain "public"."updated_domain" set default ''::text;
+comment on domain "public"."updated_domain" is 'updated domain';
+alter table "public"."domain_table" alter column "value" type character varying(10);
+drop domain "public"."used_domain";
+create domain "public"."used_domain" as text;
+alter domain "public"."used_domain" add constraint "used_domain_length" CHECK ((char_length(VALUE) < 255)) NOT VALID;
+alter table "public"."domain_table" alter column "value" type "public"."used_domain" using "value"::"public"."used_domain";
 --
 -- Constraint
 --
+alter domain "public"."updated_domain" add constraint "updated_domain_length" CHECK ((char_length(VALUE) < 255)) not valid;
+alter domain "public"."updated_domain" validate constraint "updated_domain_length";
 alter domain "public"."updated_domain" drop constraint "updated_domain_check";
 
 
@@ -105,40 +408,95 @@ create type "public"."updated_composite" as (
 );
 
 --
--- Extension
+-- Range
 --
-create extension "xml2";
-drop extension "uuid-ossp";
-alter extension "hstore" update to '1.8';
+drop type "public"."old_range";
+
+--
+-- OperatorClass
+--
+drop operator class "public"."old_class" using btree;
+
+--
+-- OperatorFamily
+--
+drop operator family "public"."old_class" using btree;
+
+--
+-- Operator
+--
+drop operator "public".!== (integer, integer);
+
+--
+-- TextSearchDictionary
+--
+drop text search dictionary "public"."old_dictionary";
+
+--
+-- TextSearchParser
+--
+drop text search parser "public"."old_parser";
+
+--
+-- Collation
+--
+drop collation "public"."old_collation";
 
 --
 -- Function
 --
-CREATE OR REPLACE FUNCTION public.new_function()
- RETURNS trigger
- LANGUAGE plpgsql
-AS $function$
-begin
-    return new;
-end;
-$function$;
 drop function "public"."old_function";
-drop function "public"."updated_function";
-CREATE OR REPLACE FUNCTION public.updated_function()
- RETURNS trigger
- LANGUAGE plpgsql
-AS $function$
-begin
-    return old;
-end;
-$function$;
+
 
 --
--- Trigger
+-- Constraint
 --
-create or replace trigger "new_trigger" AFTER UPDATE on "public"."updated_table" for each ROW EXECUTE FUNCTION new_function();
-drop trigger "old_trigger" on "public"."updated_table";
-create or replace trigger "updated_trigger" BEFORE INSERT on "public"."updated_table" for each ROW EXECUTE FUNCTION new_function();
+alter table "public"."new_author" add constraint "new_author_favorite_book_fkey" FOREIGN KEY (favorite_book) REFERENCES new_book(id);
+alter table "public"."new_book" add constraint "new_book_author_fkey" FOREIGN KEY (author) REFERENCES new_author(id);
+alter table "public"."reading_table" add constraint "reading_table_sensor_fkey" FOREIGN KEY (sensor) REFERENCES sensor_table(id);
+alter table "public"."updated_table" add constraint "updated_table_new_foreign_fkey" FOREIGN KEY (new_foreign) REFERENCES ft(id);
+alter table "public"."constrained_table" alter constraint "constrained_table_ft_id_fkey" deferrable initially deferred;
 
+--
+-- Extension
+--
+drop extension "uuid-ossp";
+
+--
+-- Cast
+--
+create cast (text as integer) with inout;
+drop cast (text as smallint);
+create cast (text as smallint) with inout as assignment;
+
+--
+-- EventTrigger
+--
+create event trigger "new_event_trigger" on ddl_command_start when tag in ('CREATE TABLE', 'ALTER TABLE') execute function event_trigger_function();
+drop event trigger "old_event_trigger";
+alter event trigger "state_event_trigger" disable;
+drop event trigger "updated_event_trigger";
+create event trigger "updated_event_trigger" on ddl_command_start execute function event_trigger_function();
+
+--
+-- Publication
+--
+create publication "new_publication" for table public.new_table with (publish = 'insert, update, delete, truncate', publish_via_partition_root = true);
+drop publication "old_publication";
+alter publication "filtered_publication" set table public.updated_table ("old_index") where ((old_index <> ''::text));
+alter publication "updated_publication" set (publish = 'insert, update', publish_via_partition_root = false);
+alter publication "updated_publication" add table public.new_table;
+alter publication "updated_publication" drop table public.ft;
 
 commit;
+
+--
+-- Subscription
+--
+create subscription "new_subscription" connection 'host=localhost dbname=new' publication "new_publication" with (connect = false);
+-- warning: password of subscription "new_subscription" isn’t copied, set it with:
+-- alter subscription "new_subscription" connection 'host=localhost dbname=new password=********';
+drop subscription "old_subscription";
+alter subscription "updated_subscription" connection 'host=remote dbname=new';
+alter subscription "updated_subscription" set publication "new_publication", "updated_publication" with (refresh = false);
+
@@ -0,0 +1,28 @@
+begin;
+
+--
+-- Role
+--
+create role "pgdiff_new_role" with createdb valid until 'infinity';
+alter role "pgdiff_new_role" set search_path = 'public';
+alter role "pgdiff_updated_role" with nologin createrole connection limit -1;
+alter role "pgdiff_updated_role" set statement_timeout = '1min';
+alter role "pgdiff_updated_role" reset work_mem;
+
+--
+-- Membership
+--
+grant "pgdiff_group" to "pgdiff_new_role";
+revoke "pgdiff_group" from "pgdiff_updated_role";
+
+--
+-- Setting
+--
+alter role "pgdiff_updated_role" in database "old_roles" set work_mem = '32MB';
+
+--
+-- Role
+--
+drop role "pgdiff_old_role";
+
+commit;
@@ -0,0 +1,15 @@
+drop role if exists pgdiff_old_role;
+
+create role pgdiff_new_role createdb password 'new_secret' valid until 'infinity';
+alter role pgdiff_new_role set search_path = public;
+grant pgdiff_group to pgdiff_new_role;
+
+alter role pgdiff_updated_role nologin createrole password 'new_secret' connection limit -1;
+alter role pgdiff_updated_role reset work_mem;
+alter role pgdiff_updated_role set statement_timeout = '1min';
+revoke pgdiff_group from pgdiff_updated_role;
+
+do $$
+begin
+    execute format('alter role pgdiff_updated_role in database %I set work_mem = %L', current_database(), '32MB');
+end$$;
@@ -0,0 +1,15 @@
+drop role if exists pgdiff_new_role;
+drop role if exists pgdiff_old_role;
+drop role if exists pgdiff_updated_role;
+drop role if exists pgdiff_group;
+
+create role pgdiff_group;
+create role pgdiff_old_role;
+create role pgdiff_updated_role login password 'old_secret' connection limit 5;
+alter role pgdiff_updated_role set work_mem = '8MB';
+grant pgdiff_group to pgdiff_updated_role;
+
+do $$
+begin
+    execute format('alter role pgdiff_updated_role in database %I set work_mem = %L', current_database(), '16MB');
+end$$;
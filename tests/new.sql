@@ -30,6 +30,11 @@ create table if not exists updated_table(
     old_foreign int,
     old_exclude circle,
     new_exclude circle,
+    widened_column varchar(20),
+    shrunk_column varchar(10),
+    bigger_column bigint,
+    json_column jsonb,
+    limited_column varchar(10),
     len varchar(10),
     new_column text,
     new_foreign int references ft(id),
@@ -37,10 +42,73 @@ create table if not exists updated_table(
 );
 
 comment on column updated_table.new_column is 'new column';
+
+create table if not exists reordered_table(
+    id int primary key,
+    created timestamptz default now(),
+    name text not null,
+    number serial
+);
+create index if not exists reordered_index on reordered_table(name);
+create table if not exists reordered_reference(
+    id int primary key,
+    reordered int references reordered_table(id)
+);
+create or replace view reordered_view as select r.id, r.name from reordered_table r;
+alter table reordered_table owner to postgres;
+grant select, insert on reordered_table to public;
+alter table reordered_table enable row level security;
+drop policy if exists reordered_policy on reordered_table;
+create policy reordered_policy on reordered_table for select to public using (id > 0);
+comment on column reordered_table.name is 'reordered column';
 comment on column updated_table.updated_column is 'updated column';
 
 create index if not exists new_index on updated_table(new_column) where new_column is null;
 create index if not exists updated_index on updated_table(updated_column) where updated_column > 10;
+create index if not exists renamed_new_index on updated_table(old_check);
+
+create table if not exists new_author(
+    id int primary key,
+    favorite_book int
+);
+
+create table if not exists new_book(
+    id int primary key,
+    author int references new_author(id)
+);
+
+do $$
+begin
+    if not exists (select 1 from pg_constraint where conname = 'new_author_favorite_book_fkey') then
+        alter table new_author add constraint new_author_favorite_book_fkey foreign key (favorite_book) references new_book(id);
+    end if;
+end$$;
+
+create table if not exists keyed_table(
+    id int primary key,
+    code text not null
+);
+
+create table if not exists unkeyed_table(
+    id int primary key
+);
+
+create table if not exists moved_key(
+    id int primary key,
+    code text not null
+);
+
+create table if not exists new_key(
+    id int primary key
+);
+
+create table if not exists constrained_table(
+    id int,
+    ft_id int,
+    constraint constrained_table_id_check check (id > 0),
+    constraint constrained_table_ft_id_fkey foreign key (ft_id) references ft(id) deferrable initially deferred,
+    constraint constrained_table_id_key unique (id) deferrable
+);
 
 do $$
 begin
@@ -54,7 +122,14 @@ begin
         create domain new_domain as text check(value ~ '^http://');
     end if;
     if not exists (select 1 from pg_type where typname = 'updated_domain') then
-        create domain updated_domain as text not null default '';
+        create domain updated_domain as text not null default '' constraint updated_domain_length check (char_length(value) < 255);
+    end if;
+    if not exists (select 1 from pg_type where typname = 'retyped_domain') then
+        create domain retyped_domain as text;
+    end if;
+    if not exists (select 1 from pg_type where typname = 'used_domain') then
+        create domain used_domain as text;
+        alter domain used_domain add constraint used_domain_length check (char_length(value) < 255) not valid;
     end if;
     if not exists (select 1 from pg_type where typname = 'new_composite') then
         create type new_composite as (name text, description varchar(255));
@@ -64,6 +139,10 @@ begin
     end if;
 end$$;
 
+create table if not exists domain_table(value used_domain);
+
+comment on domain updated_domain is 'updated domain';
+
 create extension if not exists xml2;
 create extension if not exists hstore version '1.8';
 
@@ -97,7 +176,131 @@ begin
 end;
 $$;
 
-create or replace trigger new_trigger after update on updated_table for each row execute function new_function();
+create or replace trigger new_trigger after update of new_column on updated_table for each row when (old.* is distinct from new.*) execute function new_function();
+create constraint trigger new_constraint_trigger after insert on updated_table deferrable initially deferred for each row execute function new_function();
+create or replace trigger state_trigger before update on updated_table for each row execute function trigger_function();
+alter table updated_table disable trigger state_trigger;
 create or replace trigger updated_trigger before insert on updated_table for each row execute function new_function();
 
+alter view reordered_view owner to postgres;
+grant select on reordered_view to public;
+create or replace trigger reordered_view_trigger instead of insert on reordered_view for each row execute function trigger_function();
+create or replace rule reordered_view_rule as on delete to reordered_view do instead nothing;
+create materialized view if not exists reordered_materialized_view as select r.id from reordered_table r;
+create index if not exists reordered_materialized_index on reordered_materialized_view(id);
+alter materialized view reordered_materialized_view owner to postgres;
+create or replace view reordered_new_view as select r.id, r.number from reordered_table r;
+create or replace trigger reordered_new_view_trigger instead of update on reordered_new_view for each row execute function trigger_function();
+create or replace trigger new_table_trigger before insert on new_table for each row execute function trigger_function();
+create or replace trigger new_view_trigger instead of insert on new_view for each row execute function trigger_function();
+create or replace view triggered_view as select 1 as id;
+create or replace trigger triggered_view_trigger instead of insert on triggered_view for each row execute function trigger_function();
+
+create or replace function event_trigger_function()
+    returns event_trigger
+    language plpgsql
+as $$
+begin
+end;
+$$;
+drop event trigger if exists new_event_trigger;
+create event trigger new_event_trigger on ddl_command_start when tag in ('CREATE TABLE', 'ALTER TABLE') execute function event_trigger_function();
+drop event trigger if exists updated_event_trigger;
+create event trigger updated_event_trigger on ddl_command_start execute function event_trigger_function();
+drop event trigger if exists state_event_trigger;
+create event trigger state_event_trigger on sql_drop execute function event_trigger_function();
+alter event trigger state_event_trigger disable;
+
+create extension if not exists postgres_fdw;
+create server if not exists new_server type 'postgres' foreign data wrapper postgres_fdw options (host 'remote');
+create server if not exists updated_server version '2' foreign data wrapper postgres_fdw options (host 'remote', dbname 'new');
+create user mapping if not exists for public server updated_server options (user 'postgres', password 'other');
+create user mapping if not exists for postgres server new_server options (password 'new secret');
+create foreign table if not exists new_foreign_table(id int, name text) server new_server options (table_name 'new_table');
+create foreign table if not exists updated_foreign_table(id int) server updated_server options (table_name 'other_table', schema_name 'remote');
+create server if not exists recreated_server type 'postgres' foreign data wrapper postgres_fdw options (host 'localhost');
+create user mapping if not exists for public server recreated_server options (user 'remote', password 'secret');
+create foreign table if not exists recreated_foreign_table(id int) server recreated_server options (table_name 'remote_table');
+
+drop publication if exists new_publication;
+create publication new_publication for table new_table with (publish_via_partition_root = true);
+drop publication if exists updated_publication;
+create publication updated_publication for table updated_table, reordered_table, new_table with (publish = 'insert, update');
+drop publication if exists filtered_publication;
+create publication filtered_publication for table updated_table (old_index) where (old_index <> '');
+drop subscription if exists new_subscription;
+create subscription new_subscription connection 'host=localhost dbname=new password=secret' publication new_publication with (connect = false, slot_name = none);
+drop subscription if exists updated_subscription;
+create subscription updated_subscription connection 'host=remote dbname=new' publication new_publication, updated_publication with (connect = false, slot_name = none);
+
+create or replace rule updated_rule as on update to ft do also notify ft;
+create or replace rule new_rule as on insert to updated_view do instead nothing;
+
+create statistics if not exists new_statistic (mcv) on old_index, new_check from updated_table;
+create statistics if not exists updated_statistic (ndistinct) on old_index, old_check from updated_table;
+create statistics if not exists target_statistic on old_index, new_check from updated_table;
+alter statistics target_statistic set statistics 500;
+create table if not exists measure_table(sensor int, taken date);
+create statistics if not exists measure_statistic (dependencies) on sensor, taken from measure_table;
+alter statistics measure_statistic set statistics 500;
+alter table updated_table alter column old_index set statistics 200;
+
+create unlogged table if not exists storage_table(id int, label text collate "POSIX", payload text, note text, data text compression lz4) with (fillfactor = 80, autovacuum_vacuum_scale_factor = 0.1);
+create index if not exists storage_index on storage_table(id) with (fillfactor = 90, deduplicate_items = off);
+alter table storage_table alter column payload set storage external;
+
+create table if not exists identity_table(serial_id int generated by default as identity, always_id int generated by default as identity (start with 100 increment by 10), default_id int generated by default as identity (cycle), dropped_id int not null, price int, total int generated always as (price * 3) stored, kept int, became int generated always as (price - 1) stored, added_id bigint generated always as identity, added_total int generated always as (price * 4) stored);
+
+create table if not exists price_table(widened numeric(12,2), rounded numeric(10,1), narrowed numeric(8,2), unconstrained numeric);
+
+create table if not exists sensor_table(id int primary key);
+create table if not exists reading_table(sensor int references sensor_table(id), value int check (value > 0));
+
+drop collation if exists new_collation;
+create collation new_collation (provider = icu, locale = 'und-u-ks-level2', deterministic = false);
+drop collation if exists updated_collation;
+create collation updated_collation (provider = icu, locale = 'de-DE');
+drop text search configuration if exists updated_configuration;
+drop text search configuration if exists new_configuration;
+drop text search dictionary if exists updated_dictionary;
+drop text search dictionary if exists new_dictionary;
+drop text search parser if exists new_parser;
+create text search parser new_parser (start = prsd_start, gettoken = prsd_nexttoken, end = prsd_end, lextypes = prsd_lextype, headline = prsd_headline);
+create text search dictionary new_dictionary (template = snowball, language = 'english');
+create text search dictionary updated_dictionary (template = simple, stopwords = 'french');
+create text search configuration new_configuration (parser = new_parser);
+alter text search configuration new_configuration add mapping for asciiword, word with new_dictionary;
+create text search configuration updated_configuration (copy = pg_catalog.simple);
+alter text search configuration updated_configuration alter mapping for word with new_dictionary;
+alter text search configuration updated_configuration drop mapping for url;
+
+drop cast if exists (text as integer);
+drop cast if exists (text as smallint);
+create cast (text as integer) with inout;
+create cast (text as smallint) with inout as assignment;
+drop operator family if exists new_family using btree cascade;
+drop operator if exists === (int4, int4);
+drop operator if exists ~~~ (int4, int4);
+create operator === (function = int4eq, leftarg = int4, rightarg = int4, commutator = ===, restrict = eqsel, join = eqjoinsel);
+create operator ~~~ (function = int4eq, leftarg = int4, rightarg = int4, restrict = eqsel, join = eqjoinsel);
+create operator family new_family using btree;
+create operator class new_class for type int4 using btree family new_family as operator 3 ===, function 1 btint4cmp(int4, int4);
+
+drop type if exists new_range;
+drop type if exists updated_range;
+create type new_range as range (subtype = float8, subtype_diff = float8mi);
+create type updated_range as range (subtype = numeric);
+
+do $settings$ begin
+    execute format('alter database %I reset all', current_database());
+    execute format('alter role postgres in database %I reset all', current_database());
+    execute format('alter database %I set work_mem = %L', current_database(), '16MB');
+    execute format('alter role postgres in database %I set work_mem = %L', current_database(), '32MB');
+end $settings$;
+alter default privileges for role postgres grant execute on functions to public;
+alter default privileges for role postgres in schema public grant select on tables to public;
+alter default privileges for role postgres in schema new_schema grant select on tables to public;
+
+create extension if not exists citext schema new_schema;
+
 commit;
@@ -24,12 +24,79 @@ create table if not exists updated_table(
     old_foreign int references ft(id),
     old_exclude circle,
     new_exclude circle,
+    widened_column varchar(10),
+    shrunk_column varchar(100),
+    bigger_column int,
+    json_column text,
+    limited_column text,
     exclude using gist (old_exclude with &&)
 );
 comment on table updated_table is 'need update';
 
+create table if not exists reordered_table(
+    id int primary key,
+    name text not null,
+    created timestamptz default now(),
+    number serial
+);
+create index if not exists reordered_index on reordered_table(name);
+create table if not exists reordered_reference(
+    id int primary key,
+    reordered int references reordered_table(id)
+);
+create or replace view reordered_view as select r.id, r.name from reordered_table r;
+alter table reordered_table owner to postgres;
+grant select, insert on reordered_table to public;
+alter table reordered_table enable row level security;
+drop policy if exists reordered_policy on reordered_table;
+create policy reordered_policy on reordered_table for select to public using (id > 0);
+
 create index if not exists old_index on updated_table(old_index);
 create index if not exists updated_index on updated_table(updated_column);
+create index if not exists renamed_old_index on updated_table(old_check);
+
+create table if not exists old_referenced(
+    id int primary key
+);
+
+create table if not exists old_referencing(
+    id int primary key,
+    ref int references old_referenced(id)
+);
+
+create table if not exists keyed_table(
+    id int not null,
+    code text primary key
+);
+
+create table if not exists unkeyed_table(
+    id int not null
+);
+
+create table if not exists moved_key(
+    id int not null,
+    code text primary key
+);
+create unique index if not exists moved_key_id on moved_key(id);
+
+create table if not exists new_key(
+    id int not null
+);
+create unique index if not exists new_key_id on new_key(id);
+
+create table if not exists constrained_table(
+    id int,
+    ft_id int,
+    constraint constrained_table_ft_id_fkey foreign key (ft_id) references ft(id),
+    constraint constrained_table_id_key unique (id)
+);
+
+do $$
+begin
+    if not exists (select 1 from pg_constraint where conname = 'constrained_table_id_check') then
+        alter table constrained_table add constraint constrained_table_id_check check (id > 0) not valid;
+    end if;
+end$$;
 
 do $$
 begin
@@ -45,6 +112,12 @@ begin
     if not exists (select 1 from pg_type where typname = 'updated_domain') then
         create domain updated_domain as text check (value is not null);
     end if;
+    if not exists (select 1 from pg_type where typname = 'retyped_domain') then
+        create domain retyped_domain as varchar(10);
+    end if;
+    if not exists (select 1 from pg_type where typname = 'used_domain') then
+        create domain used_domain as varchar(10);
+    end if;
     if not exists (select 1 from pg_type where typname = 'old_composite') then
         create type old_composite as ();
     end if;
@@ -53,11 +126,14 @@ begin
     end if;
 end$$;
 
+create table if not exists domain_table(value used_domain);
+
 create extension if not exists "uuid-ossp";
 create extension if not exists hstore with version '1.4';
 
 create or replace view old_view as select 1;
 create or replace view updated_view as select 1;
+create or replace view triggered_view as select 1 as id;
 create materialized view if not exists old_materialized_view as select 1;
 
 create or replace function trigger_function()
@@ -87,5 +163,111 @@ $$;
 
 create or replace trigger old_trigger before insert on updated_table for each row execute function trigger_function();
 create or replace trigger updated_trigger before insert on updated_table for each row execute function trigger_function();
+create or replace trigger state_trigger before update on updated_table for each row execute function trigger_function();
+
+alter view reordered_view owner to postgres;
+grant select on reordered_view to public;
+create or replace trigger reordered_view_trigger instead of insert on reordered_view for each row execute function trigger_function();
+create or replace rule reordered_view_rule as on delete to reordered_view do instead nothing;
+create materialized view if not exists reordered_materialized_view as select r.id from reordered_table r;
+create index if not exists reordered_materialized_index on reordered_materialized_view(id);
+alter materialized view reordered_materialized_view owner to postgres;
+
+create or replace function event_trigger_function()
+    returns event_trigger
+    language plpgsql
+as $$
+begin
+end;
+$$;
+drop event trigger if exists old_event_trigger;
+create event trigger old_event_trigger on ddl_command_end execute function event_trigger_function();
+drop event trigger if exists updated_event_trigger;
+create event trigger updated_event_trigger on ddl_command_end execute function event_trigger_function();
+drop event trigger if exists state_event_trigger;
+create event trigger state_event_trigger on sql_drop execute function event_trigger_function();
+
+create extension if not exists postgres_fdw;
+create server if not exists old_server foreign data wrapper postgres_fdw options (host 'localhost', dbname 'old');
+create server if not exists updated_server version '1' foreign data wrapper postgres_fdw options (host 'localhost', port '5432');
+create user mapping if not exists for public server updated_server options (user 'postgres', password 'secret');
+create user mapping if not exists for postgres server old_server;
+create foreign table if not exists old_foreign_table(id int) server old_server;
+create foreign table if not exists updated_foreign_table(id int) server updated_server options (table_name 'remote_table');
+create server if not exists recreated_server foreign data wrapper postgres_fdw options (host 'localhost');
+create user mapping if not exists for public server recreated_server options (user 'remote', password 'secret');
+create foreign table if not exists recreated_foreign_table(id int) server recreated_server options (table_name 'remote_table');
+
+drop publication if exists old_publication;
+create publication old_publication for all tables;
+drop publication if exists updated_publication;
+create publication updated_publication for table updated_table, reordered_table, ft, old_table with (publish = 'insert');
+drop publication if exists filtered_publication;
+create publication filtered_publication for table updated_table (old_index, new_exclude) where (old_index is not null);
+drop subscription if exists old_subscription;
+create subscription old_subscription connection 'host=localhost dbname=old password=secret' publication old_publication with (connect = false, slot_name = none);
+drop subscription if exists updated_subscription;
+create subscription updated_subscription connection 'host=localhost dbname=old' publication old_publication with (connect = false, slot_name = none);
+
+create or replace rule old_rule as on delete to ft do instead nothing;
+create or replace rule updated_rule as on update to ft do instead nothing;
+
+create statistics if not exists old_statistic (ndistinct) on old_index, old_check from updated_table;
+create statistics if not exists updated_statistic (dependencies) on old_index, old_check from updated_table;
+create statistics if not exists target_statistic on old_index, new_check from updated_table;
+
+create table if not exists storage_table(id int, label text collate "C", payload text, note text compression pglz) with (fillfactor = 70, autovacuum_enabled = false);
+create index if not exists storage_index on storage_table(id) with (fillfactor = 70);
+
+create table if not exists identity_table(serial_id serial, always_id int generated always as identity, default_id int generated by default as identity, dropped_id int generated always as identity, price int, total int generated always as (price * 2) stored, kept int generated always as (price + 1) stored, became int);
+
+create table if not exists price_table(widened numeric(10,2), rounded numeric(10,2), narrowed numeric(10,2), unconstrained numeric(10,2));
+
+create table if not exists sensor_table(id int primary key);
+create table if not exists reading_table(sensor int, value int);
+
+drop collation if exists old_collation;
+create collation old_collation (provider = libc, locale = 'C');
+drop collation if exists updated_collation;
+create collation updated_collation (provider = icu, locale = 'fr-FR');
+drop text search configuration if exists updated_configuration;
+drop text search configuration if exists old_configuration;
+drop text search dictionary if exists updated_dictionary;
+drop text search dictionary if exists old_dictionary;
+drop text search parser if exists old_parser;
+create text search parser old_parser (start = prsd_start, gettoken = prsd_nexttoken, end = prsd_end, lextypes = prsd_lextype);
+create text search dictionary old_dictionary (template = simple);
+create text search dictionary updated_dictionary (template = simple, stopwords = 'english', accept = false);
+create text search configuration old_configuration (parser = old_parser);
+create text search configuration updated_configuration (copy = pg_catalog.simple);
+alter text search configuration updated_configuration alter mapping for word with updated_dictionary, simple;
+alter text search configuration updated_configuration drop mapping for email;
+
+drop cast if exists (text as bigint);
+drop cast if exists (text as smallint);
+create cast (text as bigint) with inout as assignment;
+create cast (text as smallint) with inout;
+drop operator family if exists old_class using btree cascade;
+drop operator if exists !== (int4, int4);
+drop operator if exists ~~~ (int4, int4);
+create operator !== (function = int4ne, leftarg = int4, rightarg = int4);
+create operator ~~~ (function = int4eq, leftarg = int4, rightarg = int4);
+create operator class old_class for type int4 using btree as operator 3 !==, function 1 btint4cmp(int4, int4);
+
+drop type if exists old_range;
+drop type if exists updated_range;
+create type old_range as range (subtype = int4);
+create type updated_range as range (subtype = float8);
+
+do $settings$ begin
+    execute format('alter database %I reset all', current_database());
+    execute format('alter role postgres in database %I reset all', current_database());
+    execute format('alter database %I set work_mem = %L', current_database(), '8MB');
+    execute format('alter database %I set maintenance_work_mem = %L', current_database(), '64MB');
+end $settings$;
+alter default privileges for role postgres in schema public revoke select on tables from public;
+alter default privileges for role postgres revoke execute on functions from public;
+
+create extension if not exists citext schema public;
 
 commit;
@@ -0,0 +1,710 @@
+begin;
+
+--
+-- Cast
+--
+-- lock: none
+drop cast (text as bigint);
+
+--
+-- Setting
+--
+-- lock: none
+alter role "postgres" in database "old" set work_mem = '32MB';
+-- lock: none
+alter database "old" set work_mem = '16MB';
+-- lock: none
+alter database "old" reset maintenance_work_mem;
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.old_referencing (never analyzed, 0 pages)
+alter table "public"."old_referencing" drop constraint "old_referencing_ref_fkey";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" drop constraint "updated_table_old_foreign_fkey";
+
+--
+-- Schema
+--
+-- lock: none
+create schema new_schema;
+-- lock: none
+comment on schema new_schema is 'new schema';
+
+--
+-- DefaultPrivilege
+--
+-- lock: none
+alter default privileges for role "postgres" in schema "new_schema" grant select on tables to public;
+-- lock: none
+alter default privileges for role "postgres" in schema "public" grant select on tables to public;
+-- lock: none
+alter default privileges for role "postgres" grant execute on functions to public;
+
+--
+-- Extension
+--
+-- lock: none
+create extension "xml2" schema "public" version '1.1';
+-- lock: none
+alter extension "citext" set schema "new_schema";
+-- lock: none
+alter extension "hstore" update to '1.8';
+
+--
+-- ForeignServer
+--
+-- lock: none
+create server "new_server" type 'postgres' foreign data wrapper "postgres_fdw" options (host 'remote');
+-- lock: none
+create user mapping for "postgres" server "new_server";
+-- warning: password of user mapping for "postgres" server "new_server" isn’t copied, set it with:
+-- alter user mapping for "postgres" server "new_server" options (add password '********');
+-- lock: ACCESS EXCLUSIVE
+drop server "old_server" cascade;
+-- lock: ACCESS EXCLUSIVE
+drop server "recreated_server" cascade;
+-- lock: none
+create server "recreated_server" type 'postgres' foreign data wrapper "postgres_fdw" options (host 'localhost');
+-- lock: none
+create user mapping for public server "recreated_server" options (user 'remote');
+-- warning: password of user mapping for public server "recreated_server" isn’t copied, set it with:
+-- alter user mapping for public server "recreated_server" options (add password '********');
+-- lock: none
+alter server "updated_server" version '2';
+-- lock: none
+alter server "updated_server" options (add dbname 'new', set host 'remote', drop port);
+--
+-- UserMapping
+--
+-- warning: password of user mapping for public server "updated_server" isn’t copied, set it with:
+-- alter user mapping for public server "updated_server" options (set password '********');
+
+
+--
+-- Schema
+--
+-- lock: none
+drop schema old_schema;
+-- lock: none
+comment on schema public is 'public schema';
+--
+-- Collation
+--
+-- lock: none
+create collation "public"."new_collation" (provider = icu, locale = 'und-u-ks-level2', deterministic = false);
+-- lock: none
+drop collation "public"."updated_collation";
+-- lock: none
+create collation "public"."updated_collation" (provider = icu, locale = 'de-DE');
+
+--
+-- TextSearchParser
+--
+-- lock: none
+create text search parser "public"."new_parser" (start = prsd_start, gettoken = prsd_nexttoken, end = prsd_end, lextypes = prsd_lextype, headline = prsd_headline);
+
+--
+-- TextSearchDictionary
+--
+-- lock: none
+create text search dictionary "public"."new_dictionary" (template = pg_catalog.snowball, language = 'english');
+-- lock: none
+alter text search dictionary "public"."updated_dictionary" (stopwords = 'french', accept);
+
+--
+-- TextSearchConfiguration
+--
+-- lock: none
+create text search configuration "public"."new_configuration" (parser = public.new_parser);
+-- lock: none
+alter text search configuration "public"."new_configuration" add mapping for asciiword, word with public.new_dictionary;
+-- lock: none
+drop text search configuration "public"."old_configuration";
+-- lock: none
+alter text search configuration "public"."updated_configuration" drop mapping for url;
+-- lock: none
+alter text search configuration "public"."updated_configuration" add mapping for email with pg_catalog.simple;
+-- lock: none
+alter text search configuration "public"."updated_configuration" alter mapping for word with public.new_dictionary;
+
+--
+-- Function
+--
+-- lock: none
+CREATE OR REPLACE FUNCTION public.new_function()
+ RETURNS trigger
+ LANGUAGE plpgsql
+AS $function$
+begin
+    return new;
+end;
+$function$;
+-- lock: none
+drop function "public"."updated_function";
+-- lock: none
+CREATE OR REPLACE FUNCTION public.updated_function()
+ RETURNS trigger
+ LANGUAGE plpgsql
+AS $function$
+begin
+    return old;
+end;
+$function$;
+
+--
+-- Operator
+--
+-- lock: none
+create operator "public".=== (function = pg_catalog.int4eq, leftarg = integer, rightarg = integer, commutator = operator(public.===), restrict = eqsel, join = eqjoinsel);
+-- lock: none
+alter operator "public".~~~ (integer, integer) set (restrict = eqsel, join = eqjoinsel);
+
+--
+-- OperatorFamily
+--
+-- lock: none
+create operator family "public"."new_family" using btree;
+
+--
+-- OperatorClass
+--
+-- lock: none
+create operator class "public"."new_class" for type integer using btree family public.new_family as
+    operator 3 public.=== (integer, integer),
+    function 1 (integer, integer) btint4cmp(integer,integer);
+
+--
+-- Range
+--
+-- lock: none
+create type "public"."new_range" as range (subtype = double precision, subtype_diff = float8mi, multirange_type_name = public.new_multirange);
+-- lock: none
+drop type "public"."updated_range";
+-- lock: none
+create type "public"."updated_range" as range (subtype = numeric, multirange_type_name = public.updated_multirange);
+
+--
+-- Relation
+--
+-- lock: none
+create table "public"."measure_table"(
+    sensor int4,
+    taken date
+);
+-- lock: SHARE UPDATE EXCLUSIVE on measure_table
+CREATE STATISTICS public.measure_statistic (dependencies) ON sensor, taken FROM measure_table;
+-- lock: SHARE UPDATE EXCLUSIVE
+alter statistics "public"."measure_statistic" set statistics 500;
+-- lock: none
+create table "public"."new_author"(
+    id int4 primary key,
+    favorite_book int4
+);
+-- lock: none
+create table "public"."new_book"(
+    id int4 primary key,
+    author int4
+);
+-- lock: none
+create foreign table "public"."new_foreign_table"(
+    "id" int4,
+    "name" text
+) server "new_server" options (table_name 'new_table');
+-- lock: none
+create materialized view "public"."new_materialized_view" as  SELECT 1 AS "?column?";
+-- lock: none
+create view "public"."new_recursive_view" as  WITH RECURSIVE new_recursive_view(pk) AS (
+         SELECT 1 AS "?column?"
+        )
+ SELECT pk
+   FROM new_recursive_view;
+-- lock: none
+create table "public"."new_table"(
+    pk int4 primary key
+);
+-- lock: SHARE UPDATE EXCLUSIVE on public.new_table
+comment on table "public"."new_table" is 'new table';
+-- lock: SHARE ROW EXCLUSIVE on public.new_table
+CREATE TRIGGER new_table_trigger BEFORE INSERT ON public.new_table FOR EACH ROW EXECUTE FUNCTION trigger_function();
+-- lock: none
+create unlogged table "public"."new_unlogged_table"(
+);
+-- lock: none
+create view "public"."new_view" as  SELECT pk
+   FROM new_table;
+-- lock: SHARE ROW EXCLUSIVE on public.new_view
+CREATE TRIGGER new_view_trigger INSTEAD OF INSERT ON public.new_view FOR EACH ROW EXECUTE FUNCTION trigger_function();
+-- lock: none
+create foreign table "public"."recreated_foreign_table"(
+    "id" int4
+) server "recreated_server" options (table_name 'remote_table');
+-- lock: none
+create view "public"."reordered_new_view" as  SELECT r.id,
+    r.number
+   FROM reordered_table r;
+-- lock: SHARE ROW EXCLUSIVE on public.reordered_new_view
+CREATE TRIGGER reordered_new_view_trigger INSTEAD OF UPDATE ON public.reordered_new_view FOR EACH ROW EXECUTE FUNCTION trigger_function();
+-- lock: ACCESS EXCLUSIVE on public.old_foreign_table (never analyzed, 0 pages)
+drop foreign table if exists "public"."old_foreign_table";
+-- lock: ACCESS EXCLUSIVE on public.old_materialized_view (never analyzed, 0 pages)
+drop materialized view "public"."old_materialized_view";
+-- lock: ACCESS EXCLUSIVE on public.old_referenced (never analyzed, 0 pages)
+drop table "public"."old_referenced";
+-- lock: ACCESS EXCLUSIVE on public.old_referencing (never analyzed, 0 pages)
+drop table "public"."old_referencing";
+-- lock: ACCESS EXCLUSIVE on public.old_table (never analyzed, 0 pages)
+drop table "public"."old_table";
+-- lock: ACCESS EXCLUSIVE on public.old_view (never analyzed, 0 pages)
+drop view "public"."old_view";
+-- warning: column order of "public"."identity_table" differs, expected "serial_id", "always_id", "default_id", "dropped_id", "price", "total", "kept", "became", "added_id", "added_total"
+-- warning: column order of "public"."reordered_table" differs, expected "id", "created", "name", "number"
+-- lock: ACCESS EXCLUSIVE on public.storage_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."storage_table" set unlogged;
+-- lock: SHARE UPDATE EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter table "public"."storage_table" set (autovacuum_vacuum_scale_factor=0.1, fillfactor=80);
+-- lock: SHARE UPDATE EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter table "public"."storage_table" reset (autovacuum_enabled);
+-- lock: ACCESS EXCLUSIVE on public.updated_foreign_table (never analyzed, 0 pages)
+alter foreign table "public"."updated_foreign_table" options (add schema_name 'remote', set table_name 'other_table');
+-- lock: SHARE UPDATE EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+comment on table "public"."updated_table" is null;
+-- lock: ACCESS EXCLUSIVE on public.updated_view (never analyzed, 0 pages)
+drop view "public"."updated_view";
+-- lock: none
+create view "public"."updated_view" as  SELECT pk
+   FROM new_table;
+--
+-- Constraint
+--
+-- lock: SHARE UPDATE EXCLUSIVE on public.constrained_table (never analyzed, 0 pages), scans the table
+alter table "public"."constrained_table" validate constraint "constrained_table_id_check";
+-- lock: ACCESS EXCLUSIVE on public.constrained_table (never analyzed, 0 pages)
+alter table "public"."constrained_table" drop constraint "constrained_table_id_key";
+-- lock: ACCESS EXCLUSIVE on public.constrained_table (never analyzed, 0 pages), scans the table
+alter table "public"."constrained_table" add constraint "constrained_table_id_key" UNIQUE (id) DEFERRABLE;
+
+--
+-- Rule
+--
+-- lock: ACCESS EXCLUSIVE on public.ft (never analyzed, 0 pages)
+drop rule "old_rule" on "public"."ft";
+-- lock: ACCESS EXCLUSIVE on public.ft (never analyzed, 0 pages)
+CREATE OR REPLACE RULE updated_rule AS
+    ON UPDATE TO public.ft DO
+ NOTIFY ft;
+
+--
+-- Column
+--
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."identity_table" add column "added_id" int8 generated always as identity (start with 1 increment by 1 minvalue 1 maxvalue 9223372036854775807 cache 1 no cycle);
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."identity_table" add column "added_total" int4 generated always as ((price * 4)) stored;
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" alter column "always_id" set generated by default set start with 100 set increment by 10;
+-- warning: recreating generated column "public"."identity_table"."became"
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" drop column "became";
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."identity_table" add column "became" int4 generated always as ((price - 1)) stored;
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" alter column "default_id" set cycle;
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" alter column "dropped_id" drop identity;
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" alter column "kept" drop expression;
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" alter column "serial_id" drop default;
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" alter column "serial_id" add generated by default as identity (start with 1 increment by 1 minvalue 1 maxvalue 2147483647 cache 1 no cycle);
+-- warning: recreating generated column "public"."identity_table"."total"
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages)
+alter table "public"."identity_table" drop column "total";
+-- lock: ACCESS EXCLUSIVE on public.identity_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."identity_table" add column "total" int4 generated always as ((price * 3)) stored;
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.keyed_table (never analyzed, 0 pages)
+alter table "public"."keyed_table" drop constraint "keyed_table_pkey";
+-- lock: ACCESS EXCLUSIVE on public.keyed_table (never analyzed, 0 pages), scans the table
+alter table "public"."keyed_table" add constraint "keyed_table_pkey" PRIMARY KEY (id);
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.moved_key (never analyzed, 0 pages)
+alter table "public"."moved_key" drop constraint "moved_key_pkey", add constraint "moved_key_pkey" primary key using index "moved_key_id";
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.new_key (never analyzed, 0 pages)
+alter table "public"."new_key" add constraint "new_key_pkey" primary key using index "new_key_id";
+
+--
+-- Column
+--
+-- warning: converting "public"."price_table"."narrowed" from numeric(10,2) to numeric(8,2) may lose data
+-- lock: ACCESS EXCLUSIVE on public.price_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."price_table" alter column "narrowed" type numeric(8,2) using "narrowed"::numeric(8,2);
+-- warning: converting "public"."price_table"."rounded" from numeric(10,2) to numeric(10,1) may lose data
+-- lock: ACCESS EXCLUSIVE on public.price_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."price_table" alter column "rounded" type numeric(10,1) using "rounded"::numeric(10,1);
+-- lock: ACCESS EXCLUSIVE on public.price_table (never analyzed, 0 pages)
+alter table "public"."price_table" alter column "unconstrained" type numeric;
+-- lock: ACCESS EXCLUSIVE on public.price_table (never analyzed, 0 pages)
+alter table "public"."price_table" alter column "widened" type numeric(12,2);
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.reading_table (never analyzed, 0 pages), scans the table
+alter table "public"."reading_table" add constraint "reading_table_value_check" CHECK ((value > 0));
+
+--
+-- Column
+--
+-- lock: SHARE UPDATE EXCLUSIVE on public.reordered_table (never analyzed, 0 pages)
+comment on column "public"."reordered_table"."name" is 'reordered column';
+
+--
+-- Column
+--
+-- lock: ACCESS EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter table "public"."storage_table" add column "data" text;
+-- lock: ACCESS EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter table "public"."storage_table" alter column "data" set compression lz4;
+-- lock: ACCESS EXCLUSIVE on public.storage_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."storage_table" alter column "label" type text collate pg_catalog."POSIX";
+-- lock: ACCESS EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter table "public"."storage_table" alter column "note" set compression default;
+-- lock: ACCESS EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter table "public"."storage_table" alter column "payload" set storage external;
+
+--
+-- Index
+--
+-- lock: SHARE UPDATE EXCLUSIVE on public.storage_table (never analyzed, 0 pages)
+alter index "public"."storage_index" set (deduplicate_items=off, fillfactor=90);
+
+--
+-- Trigger
+--
+-- lock: SHARE ROW EXCLUSIVE on public.triggered_view (never analyzed, 0 pages)
+CREATE TRIGGER triggered_view_trigger INSTEAD OF INSERT ON public.triggered_view FOR EACH ROW EXECUTE FUNCTION trigger_function();
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.unkeyed_table (never analyzed, 0 pages), scans the table
+alter table "public"."unkeyed_table" add constraint "unkeyed_table_pkey" PRIMARY KEY (id);
+
+--
+-- Column
+--
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" add column "len" varchar(10);
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" add column "new_column" text;
+-- lock: SHARE UPDATE EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+comment on column "public"."updated_table"."new_column" is 'new column';
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" add column "new_foreign" int4;
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" drop column "old_column";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."updated_table" alter column "bigger_column" type int8;
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."updated_table" alter column "json_column" type jsonb using "json_column"::jsonb;
+-- warning: converting "public"."updated_table"."limited_column" from text to varchar(10) may lose data
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."updated_table" alter column "limited_column" type varchar(10) using "limited_column"::varchar(10);
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" alter column "new_default" set default now();
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), scans the table
+alter table "public"."updated_table" alter column "new_not_null" set not null;
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" alter column "old_default" drop default;
+-- lock: SHARE UPDATE EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" alter column "old_index" set statistics 200;
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" alter column "old_not_null" drop not null;
+-- warning: converting "public"."updated_table"."shrunk_column" from varchar(100) to varchar(10) may lose data
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."updated_table" alter column "shrunk_column" type varchar(10) using "shrunk_column"::varchar(10);
+-- lock: SHARE UPDATE EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+comment on column "public"."updated_table"."updated_column" is 'updated column';
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."updated_table" alter column "updated_column" type int4 using "updated_column"::int4;
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" alter column "widened_column" type varchar(20);
+
+--
+-- Constraint
+--
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), scans the table
+alter table "public"."updated_table" add constraint "updated_table_new_check_check" CHECK ((char_length(new_check) = 5));
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), scans the table
+alter table "public"."updated_table" add constraint "updated_table_new_exclude_excl" EXCLUDE USING gist (new_exclude WITH &&);
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), scans the table
+alter table "public"."updated_table" add constraint "updated_table_new_unique_key" UNIQUE (new_unique);
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" drop constraint "updated_table_old_check_check";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" drop constraint "updated_table_old_exclude_excl";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" drop constraint "updated_table_old_unique_key";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" drop constraint "updated_table_updated_check_check";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages), scans the table
+alter table "public"."updated_table" add constraint "updated_table_updated_check_check" CHECK ((char_length(updated_check) = 2));
+
+--
+-- Index
+--
+-- lock: SHARE on public.updated_table (never analyzed, 0 pages), scans the table
+CREATE INDEX new_index ON public.updated_table USING btree (new_column) WHERE (new_column IS NULL);
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+drop index "public"."old_index";
+-- lock: SHARE UPDATE EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter index "public"."renamed_old_index" rename to "renamed_new_index";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+drop index "public"."updated_index";
+-- lock: SHARE on public.updated_table (never analyzed, 0 pages), scans the table
+CREATE INDEX updated_index ON public.updated_table USING btree (updated_column) WHERE (updated_column > 10);
+
+--
+-- Trigger
+--
+-- lock: SHARE ROW EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+CREATE CONSTRAINT TRIGGER new_constraint_trigger AFTER INSERT ON public.updated_table DEFERRABLE INITIALLY DEFERRED FOR EACH ROW EXECUTE FUNCTION new_function();
+-- lock: SHARE ROW EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+CREATE TRIGGER new_trigger AFTER UPDATE OF new_column ON public.updated_table FOR EACH ROW WHEN ((old.* IS DISTINCT FROM new.*)) EXECUTE FUNCTION new_function();
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+drop trigger "old_trigger" on "public"."updated_table";
+-- lock: SHARE ROW EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter table "public"."updated_table" disable trigger "state_trigger";
+-- lock: ACCESS EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+drop trigger "updated_trigger" on "public"."updated_table";
+-- lock: SHARE ROW EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+CREATE TRIGGER updated_trigger BEFORE INSERT ON public.updated_table FOR EACH ROW EXECUTE FUNCTION new_function();
+
+--
+-- Statistic
+--
+-- lock: SHARE UPDATE EXCLUSIVE on updated_table
+CREATE STATISTICS public.new_statistic (mcv) ON old_index, new_check FROM updated_table;
+-- lock: SHARE UPDATE EXCLUSIVE
+drop statistics "public"."old_statistic";
+-- lock: SHARE UPDATE EXCLUSIVE
+alter statistics "public"."target_statistic" set statistics 500;
+-- lock: SHARE UPDATE EXCLUSIVE
+drop statistics "public"."updated_statistic";
+-- lock: SHARE UPDATE EXCLUSIVE on updated_table
+CREATE STATISTICS public.updated_statistic (ndistinct) ON old_index, old_check FROM updated_table;
+
+--
+-- Rule
+--
+-- lock: ACCESS EXCLUSIVE on public.updated_view (never analyzed, 0 pages)
+CREATE OR REPLACE RULE new_rule AS
+    ON INSERT TO public.updated_view DO INSTEAD NOTHING;
+
+
+--
+-- Enum
+--
+-- lock: none
+create type "public"."new_enum" as enum('sad', 'ok', 'happy');
+-- lock: none
+drop type "public"."old_enum";
+-- lock: none
+delete from pg_enum e using pg_type t, pg_namespace n where e.enumtypid = t.oid and t.typname = 'updated_enum' and t.typnamespace = n.oid and n.nspname = 'public' and enumlabel = 'happy';
+-- lock: none
+alter type "public"."updated_enum" add value 'neutral' after 'sad';
+
+--
+-- Domain
+--
+-- lock: none
+create domain "public"."new_domain" as text constraint "new_domain_check" CHECK ((VALUE ~ '^http://'::text));
+-- lock: none
+drop domain "public"."old_domain";
+-- lock: none
+drop domain "public"."retyped_domain";
+-- lock: none
+create domain "public"."retyped_domain" as text;
+-- lock: SHARE, scans the table
+alter domain "public"."updated_domain" set not null;
+-- lock: none
+alter domain "public"."updated_domain" set default ''::text;
+-- lock: none
+comment on domain "public"."updated_domain" is 'updated domain';
+-- lock: ACCESS EXCLUSIVE on public.domain_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."domain_table" alter column "value" type character varying(10);
+-- lock: none
+drop domain "public"."used_domain";
+-- lock: none
+create domain "public"."used_domain" as text;
+-- lock: none
+alter domain "public"."used_domain" add constraint "used_domain_length" CHECK ((char_length(VALUE) < 255)) NOT VALID;
+-- lock: ACCESS EXCLUSIVE on public.domain_table (never analyzed, 0 pages), rewrites the table
+alter table "public"."domain_table" alter column "value" type "public"."used_domain" using "value"::"public"."used_domain";
+--
+-- Constraint
+--
+-- lock: none
+alter domain "public"."updated_domain" add constraint "updated_domain_length" CHECK ((char_length(VALUE) < 255)) not valid;
+-- lock: SHARE, scans the table
+alter domain "public"."updated_domain" validate constraint "updated_domain_length";
+-- lock: none
+alter domain "public"."updated_domain" drop constraint "updated_domain_check";
+
+
+--
+-- Composite
+--
+-- lock: none
+create type "public"."new_composite" as (
+    name text,
+    description varchar(255)
+);
+-- lock: none
+drop type "public"."old_composite";
+-- lock: none
+drop type "public"."updated_composite";
+-- lock: none
+create type "public"."updated_composite" as (
+    r float8,
+    i float8
+);
+
+--
+-- Range
+--
+-- lock: none
+drop type "public"."old_range";
+
+--
+-- OperatorClass
+--
+-- lock: none
+drop operator class "public"."old_class" using btree;
+
+--
+-- OperatorFamily
+--
+-- lock: none
+drop operator family "public"."old_class" using btree;
+
+--
+-- Operator
+--
+-- lock: none
+drop operator "public".!== (integer, integer);
+
+--
+-- TextSearchDictionary
+--
+-- lock: none
+drop text search dictionary "public"."old_dictionary";
+
+--
+-- TextSearchParser
+--
+-- lock: none
+drop text search parser "public"."old_parser";
+
+--
+-- Collation
+--
+-- lock: none
+drop collation "public"."old_collation";
+
+--
+-- Function
+--
+-- lock: none
+drop function "public"."old_function";
+
+
+--
+-- Constraint
+--
+-- lock: SHARE ROW EXCLUSIVE on public.new_author, scans the table
+alter table "public"."new_author" add constraint "new_author_favorite_book_fkey" FOREIGN KEY (favorite_book) REFERENCES new_book(id);
+-- lock: SHARE ROW EXCLUSIVE on public.new_book, scans the table
+alter table "public"."new_book" add constraint "new_book_author_fkey" FOREIGN KEY (author) REFERENCES new_author(id);
+-- lock: SHARE ROW EXCLUSIVE on public.reading_table (never analyzed, 0 pages), scans the table
+alter table "public"."reading_table" add constraint "reading_table_sensor_fkey" FOREIGN KEY (sensor) REFERENCES sensor_table(id);
+-- lock: SHARE ROW EXCLUSIVE on public.updated_table (never analyzed, 0 pages), scans the table
+alter table "public"."updated_table" add constraint "updated_table_new_foreign_fkey" FOREIGN KEY (new_foreign) REFERENCES ft(id);
+-- lock: SHARE ROW EXCLUSIVE on public.constrained_table (never analyzed, 0 pages)
+alter table "public"."constrained_table" alter constraint "constrained_table_ft_id_fkey" deferrable initially deferred;
+
+--
+-- Extension
+--
+-- lock: none
+drop extension "uuid-ossp";
+
+--
+-- Cast
+--
+-- lock: none
+create cast (text as integer) with inout;
+-- lock: none
+drop cast (text as smallint);
+-- lock: none
+create cast (text as smallint) with inout as assignment;
+
+--
+-- EventTrigger
+--
+-- lock: none
+create event trigger "new_event_trigger" on ddl_command_start when tag in ('CREATE TABLE', 'ALTER TABLE') execute function event_trigger_function();
+-- lock: none
+drop event trigger "old_event_trigger";
+-- lock: none
+alter event trigger "state_event_trigger" disable;
+-- lock: none
+drop event trigger "updated_event_trigger";
+-- lock: none
+create event trigger "updated_event_trigger" on ddl_command_start execute function event_trigger_function();
+
+--
+-- Publication
+--
+-- lock: SHARE UPDATE EXCLUSIVE on public.new_table
+create publication "new_publication" for table public.new_table with (publish = 'insert, update, delete, truncate', publish_via_partition_root = true);
+-- lock: none
+drop publication "old_publication";
+-- lock: SHARE UPDATE EXCLUSIVE on public.updated_table (never analyzed, 0 pages)
+alter publication "filtered_publication" set table public.updated_table ("old_index") where ((old_index <> ''::text));
+-- lock: none
+alter publication "updated_publication" set (publish = 'insert, update', publish_via_partition_root = false);
+-- lock: SHARE UPDATE EXCLUSIVE on public.new_table
+alter publication "updated_publication" add table public.new_table;
+-- lock: SHARE UPDATE EXCLUSIVE on public.ft (never analyzed, 0 pages)
+alter publication "updated_publication" drop table public.ft;
+
+commit;
+
+--
+-- Subscription
+--
+-- lock: none
+create subscription "new_subscription" connection 'host=localhost dbname=new' publication "new_publication" with (connect = false);
+-- warning: password of subscription "new_subscription" isn’t copied, set it with:
+-- alter subscription "new_subscription" connection 'host=localhost dbname=new password=********';
+-- lock: none
+drop subscription "old_subscription";
+-- lock: none
+alter subscription "updated_subscription" connection 'host=remote dbname=new';
+-- lock: none
+alter subscription "updated_subscription" set publication "new_publication", "updated_publication" with (refresh = false);
+